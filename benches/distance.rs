@@ -0,0 +1,77 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use pgvector::{Bit, HalfVector, SparseVector, Vector};
+
+// deterministic values in [-1, 1)
+fn values(n: usize, seed: u32) -> Vec<f32> {
+    let mut state = seed;
+    (0..n)
+        .map(|_| {
+            state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+            (state >> 8) as f32 / (1 << 23) as f32 - 1.0
+        })
+        .collect()
+}
+
+fn vector(c: &mut Criterion) {
+    let a = Vector::from(values(1536, 1));
+    let b = Vector::from(values(1536, 2));
+    c.bench_function("vector l2_distance", |bench| {
+        bench.iter(|| black_box(&a).l2_distance(black_box(&b)))
+    });
+    c.bench_function("vector inner_product", |bench| {
+        bench.iter(|| black_box(&a).inner_product(black_box(&b)))
+    });
+    c.bench_function("vector cosine_distance", |bench| {
+        bench.iter(|| black_box(&a).cosine_distance(black_box(&b)))
+    });
+    c.bench_function("vector l1_distance", |bench| {
+        bench.iter(|| black_box(&a).l1_distance(black_box(&b)))
+    });
+}
+
+fn halfvec(c: &mut Criterion) {
+    let a = HalfVector::from_f32_slice(&values(1536, 3));
+    let b = HalfVector::from_f32_slice(&values(1536, 4));
+    c.bench_function("halfvec l2_distance", |bench| {
+        bench.iter(|| black_box(&a).l2_distance(black_box(&b)))
+    });
+    c.bench_function("halfvec inner_product", |bench| {
+        bench.iter(|| black_box(&a).inner_product(black_box(&b)))
+    });
+    c.bench_function("halfvec cosine_distance", |bench| {
+        bench.iter(|| black_box(&a).cosine_distance(black_box(&b)))
+    });
+}
+
+fn sparsevec(c: &mut Criterion) {
+    // learned sparse vectors over a 30k vocabulary
+    let sparse = |n: usize, step: usize, seed: u32| {
+        let mut dense = vec![0.0; 30000];
+        for (i, v) in values(n, seed).into_iter().enumerate() {
+            dense[i * step] = v;
+        }
+        SparseVector::from_dense(&dense)
+    };
+    let a = sparse(50, 97, 5);
+    let b = sparse(2000, 13, 6);
+    c.bench_function("sparsevec inner_product", |bench| {
+        bench.iter(|| black_box(&a).inner_product(black_box(&b)))
+    });
+    c.bench_function("sparsevec cosine_distance", |bench| {
+        bench.iter(|| black_box(&a).cosine_distance(black_box(&b)))
+    });
+}
+
+fn bit(c: &mut Criterion) {
+    let a = Bit::from_bytes(&(0..192).map(|i| (i * 37 + 11) as u8).collect::<Vec<u8>>());
+    let b = Bit::from_bytes(&(0..192).map(|i| (i * 91 + 5) as u8).collect::<Vec<u8>>());
+    c.bench_function("bit hamming_distance", |bench| {
+        bench.iter(|| black_box(&a).hamming_distance(black_box(&b)))
+    });
+    c.bench_function("bit jaccard_distance", |bench| {
+        bench.iter(|| black_box(&a).jaccard_distance(black_box(&b)))
+    });
+}
+
+criterion_group!(benches, vector, halfvec, sparsevec, bit);
+criterion_main!(benches);
@@ -40,7 +40,7 @@ fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
     for (content, embedding) in input.iter().zip(embeddings) {
         client.execute(
             "INSERT INTO documents (content, embedding) VALUES ($1, $2)",
-            &[&content, &Vector::from(embedding)],
+            &[&content, &embedding],
         )?;
     }
 
@@ -82,13 +82,13 @@ impl EmbeddingModel {
     }
 
     // TODO support multiple texts
-    fn embed(&self, text: &str) -> Result<Vec<f32>, Box<dyn Error + Send + Sync>> {
+    fn embed(&self, text: &str) -> Result<Vector, Box<dyn Error + Send + Sync>> {
         let tokens = self.tokenizer.encode(text, true)?;
         let token_ids = Tensor::new(vec![tokens.get_ids().to_vec()], &self.model.device)?;
         let token_type_ids = token_ids.zeros_like()?;
         let embeddings = self.model.forward(&token_ids, &token_type_ids, None)?;
         let embeddings = (embeddings.sum(1)? / (embeddings.dim(1)? as f64))?;
         let embeddings = embeddings.broadcast_div(&embeddings.sqr()?.sum_keepdim(1)?.sqrt()?)?;
-        Ok(embeddings.squeeze(0)?.to_vec1::<f32>()?)
+        Ok(Vector::try_from(embeddings.squeeze(0)?)?)
     }
 }
@@ -1,6 +1,6 @@
 use pgvector::Vector;
 use postgres::binary_copy::BinaryCopyInWriter;
-use postgres::types::{Kind, Type};
+use postgres::types::Type;
 use postgres::{Client, NoTls};
 use rand::Rng;
 use std::error::Error;
@@ -59,7 +59,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     )?;
 
     println!("Loading data in parallel");
-    let vector_type = get_type(&mut client, "vector")?;
+    let vector_type = pgvector::postgres::types(&mut client)?.vector().clone();
     let writer =
         client.copy_in("COPY items (embedding, category_id) FROM STDIN WITH (FORMAT BINARY)")?;
     let mut writer = BinaryCopyInWriter::new(writer, &[vector_type, Type::INT8]);
@@ -86,13 +86,3 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
-
-fn get_type(client: &mut Client, name: &str) -> Result<Type, Box<dyn Error>> {
-    let row = client.query_one("SELECT pg_type.oid, nspname AS schema FROM pg_type INNER JOIN pg_namespace ON pg_namespace.oid = pg_type.typnamespace WHERE typname = $1", &[&name])?;
-    Ok(Type::new(
-        name.into(),
-        row.get("oid"),
-        Kind::Simple,
-        row.get("schema"),
-    ))
-}
@@ -1,19 +1,26 @@
+use pgvector::vecs::FvecsReader;
 use pgvector::Vector;
 use postgres::binary_copy::BinaryCopyInWriter;
-use postgres::types::{Kind, Type};
 use postgres::{Client, NoTls};
 use rand::Rng;
 use std::error::Error;
 use std::io::{self, Write};
 
 fn main() -> Result<(), Box<dyn Error>> {
-    // generate random data
-    let rows = 1000000;
-    let dimensions = 128;
-    let mut rng = rand::rng();
-    let embeddings: Vec<Vec<f32>> = (0..rows)
-        .map(|_| (0..dimensions).map(|_| rng.random()).collect())
-        .collect();
+    // stream an .fvecs file (like sift_base.fvecs) or generate random data
+    let fvecs = std::env::var("FVECS");
+    let mut embeddings: Box<dyn Iterator<Item = io::Result<Vector>>> = match fvecs {
+        Ok(path) => Box::new(FvecsReader::open(path)?),
+        Err(_) => {
+            let mut rng = rand::rng();
+            Box::new((0..1000000).map(move |_| {
+                let vec: Vec<f32> = (0..128).map(|_| rng.random()).collect();
+                Ok(Vector::from(vec))
+            }))
+        }
+    };
+    let first = embeddings.next().ok_or("no vectors")??;
+    let dimensions = first.dimensions();
 
     // enable extension
     let mut client = Client::configure()
@@ -31,12 +38,12 @@ fn main() -> Result<(), Box<dyn Error>> {
     )?;
 
     // load data
-    println!("Loading {} rows", embeddings.len());
-    let vector_type = get_type(&mut client, "vector")?;
+    println!("Loading rows");
+    let vector_type = pgvector::postgres::types(&mut client)?.vector().clone();
     let writer = client.copy_in("COPY items (embedding) FROM STDIN WITH (FORMAT BINARY)")?;
     let mut writer = BinaryCopyInWriter::new(writer, &[vector_type]);
-    for (i, embedding) in embeddings.into_iter().enumerate() {
-        writer.write(&[&Vector::from(embedding)])?;
+    for (i, embedding) in std::iter::once(Ok(first)).chain(embeddings).enumerate() {
+        writer.write(&[&embedding?])?;
 
         // show progress
         if i % 10000 == 0 {
@@ -63,13 +70,3 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
-
-fn get_type(client: &mut Client, name: &str) -> Result<Type, Box<dyn Error>> {
-    let row = client.query_one("SELECT pg_type.oid, nspname AS schema FROM pg_type INNER JOIN pg_namespace ON pg_namespace.oid = pg_type.typnamespace WHERE typname = $1", &[&name])?;
-    Ok(Type::new(
-        name.into(),
-        row.get("oid"),
-        Kind::Simple,
-        row.get("schema"),
-    ))
-}
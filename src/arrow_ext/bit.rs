@@ -0,0 +1,112 @@
+use arrow_array::{Array, FixedSizeBinaryArray};
+
+use crate::{limits, Bit, Error};
+
+/// Creates bit strings from a `FixedSizeBinaryArray`.
+///
+/// Each bit string has 8 bits per byte and null rows become `None`.
+pub fn from_bit_array(array: &dyn Array) -> Result<Vec<Option<Bit>>, Error> {
+    Ok(iter_bit_array(array)?.collect())
+}
+
+/// Creates bit strings with a length in bits from a `FixedSizeBinaryArray`.
+///
+/// Use this for arrays from `to_bit_array` when the length is not a multiple of 8.
+pub fn from_bit_array_with_len(array: &dyn Array, len: usize) -> Result<Vec<Option<Bit>>, Error> {
+    iter_bit_array_with_len(array, len)?.collect()
+}
+
+/// Returns an iterator that creates bit strings from a `FixedSizeBinaryArray` one row at a time.
+pub fn iter_bit_array(
+    array: &dyn Array,
+) -> Result<impl ExactSizeIterator<Item = Option<Bit>> + '_, Error> {
+    Ok(binary_array(array)?.iter().map(|v| v.map(Bit::from_bytes)))
+}
+
+/// Returns an iterator that creates bit strings with a length in bits from a `FixedSizeBinaryArray` one row at a time.
+pub fn iter_bit_array_with_len(
+    array: &dyn Array,
+    len: usize,
+) -> Result<impl ExactSizeIterator<Item = Result<Option<Bit>, Error>> + '_, Error> {
+    Ok(binary_array(array)?
+        .iter()
+        .map(move |v| v.map(|v| Bit::from_bytes_with_len(v, len)).transpose()))
+}
+
+fn binary_array(array: &dyn Array) -> Result<&FixedSizeBinaryArray, Error> {
+    array
+        .as_any()
+        .downcast_ref::<FixedSizeBinaryArray>()
+        .ok_or_else(|| super::unsupported(array, "bit"))
+}
+
+/// Creates a `FixedSizeBinaryArray` from bit strings with the same length.
+///
+/// Lengths that are not a multiple of 8 are padded with zeros, so read them back with `from_bit_array_with_len`.
+pub fn to_bit_array(vecs: &[Option<Bit>]) -> Result<FixedSizeBinaryArray, Error> {
+    let len = limits::same_len(vecs.iter().flatten().map(|v| v.len()), "bit")?;
+    let size = len.map(|v| (v + 7) / 8).unwrap_or(0);
+
+    let iter = vecs.iter().map(|v| v.as_ref().map(|v| v.as_bytes()));
+    // cannot fail since every row has the same size
+    Ok(
+        FixedSizeBinaryArray::try_from_sparse_iter_with_size(iter, i32::try_from(size).unwrap())
+            .unwrap(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arrow::{
+        from_bit_array, from_bit_array_with_len, iter_bit_array, iter_bit_array_with_len,
+        to_bit_array,
+    };
+    use crate::{Bit, Error};
+    use arrow_array::Array;
+
+    #[test]
+    fn test_bit_array() {
+        let vecs = vec![
+            Some(Bit::from_bytes(&[0b00000000, 0b11111111])),
+            None,
+            Some(Bit::from_bytes(&[0b10101010, 0b01010101])),
+        ];
+        let array = to_bit_array(&vecs).unwrap();
+        assert_eq!(2, array.value_length());
+        assert!(array.is_null(1));
+        assert_eq!(vecs, from_bit_array(&array).unwrap());
+        assert_eq!(vecs, iter_bit_array(&array).unwrap().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_bit_array_with_len() {
+        let vecs = vec![Some(Bit::new(&[true, false, true])), None];
+        let array = to_bit_array(&vecs).unwrap();
+        assert_eq!(1, array.value_length());
+        assert_eq!(vecs, from_bit_array_with_len(&array, 3).unwrap());
+        let mut iter = iter_bit_array_with_len(&array, 3).unwrap();
+        assert_eq!(2, iter.len());
+        assert_eq!(Some(Ok(vecs[0].clone())), iter.next());
+        assert_eq!(
+            Err(Error::InvalidLength {
+                type_name: "bit",
+                expected: 2,
+                actual: 1
+            }),
+            from_bit_array_with_len(&array, 9)
+        );
+    }
+
+    #[test]
+    fn test_bit_array_different_lengths() {
+        let vecs = vec![Some(Bit::new(&[true])), Some(Bit::new(&[true, false]))];
+        assert_eq!(
+            Err(Error::DifferentDimensions {
+                type_name: "bit",
+                left: 1,
+                right: 2
+            }),
+            to_bit_array(&vecs)
+        );
+    }
+}
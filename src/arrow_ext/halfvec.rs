@@ -0,0 +1,82 @@
+use arrow_array::types::Float16Type;
+use arrow_array::{Array, FixedSizeListArray, ListArray};
+use half::f16;
+
+use crate::{Error, HalfVector, HalfVectorRef};
+
+/// Borrows half vectors from a list array of `Float16` values, like a `FixedSizeListArray`.
+///
+/// Rows are read as the iterator advances and null rows become `None`.
+pub fn half_refs_from_array(
+    array: &dyn Array,
+) -> Result<impl ExactSizeIterator<Item = Result<Option<HalfVectorRef<'_>>, Error>> + '_, Error> {
+    Ok(super::list_rows::<Float16Type>(array, "halfvec")?
+        .map(|v| v.map(|v| v.map(HalfVectorRef::from))))
+}
+
+/// Creates half vectors from a list array of `Float16` values, like a `FixedSizeListArray`.
+///
+/// Null rows become `None`.
+pub fn from_half_array(array: &dyn Array) -> Result<Vec<Option<HalfVector>>, Error> {
+    super::list_rows::<Float16Type>(array, "halfvec")?
+        .map(|v| v.map(|v| v.map(|v| HalfVector::from(v.to_vec()))))
+        .collect()
+}
+
+/// Creates a `FixedSizeListArray` from half vectors with the same number of dimensions.
+pub fn to_half_fixed_size_list_array(
+    vecs: &[Option<HalfVector>],
+) -> Result<FixedSizeListArray, Error> {
+    super::to_fixed_size_list::<Float16Type>(&rows(vecs), "halfvec")
+}
+
+/// Creates a `ListArray` from half vectors.
+pub fn to_half_list_array(vecs: &[Option<HalfVector>]) -> ListArray {
+    super::to_list::<Float16Type>(&rows(vecs))
+}
+
+fn rows(vecs: &[Option<HalfVector>]) -> Vec<Option<&[f16]>> {
+    vecs.iter()
+        .map(|v| v.as_ref().map(|v| v.as_slice()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arrow::{
+        from_half_array, half_refs_from_array, to_half_fixed_size_list_array, to_half_list_array,
+    };
+    use crate::HalfVector;
+    use arrow_array::types::Float32Type;
+    use arrow_array::FixedSizeListArray;
+
+    #[test]
+    fn test_half_array() {
+        let vecs = vec![
+            Some(HalfVector::from_f32_slice(&[1.0, 2.0])),
+            None,
+            Some(HalfVector::from_f32_slice(&[3.0, 4.0])),
+        ];
+
+        let array = to_half_fixed_size_list_array(&vecs).unwrap();
+        assert_eq!(vecs, from_half_array(&array).unwrap());
+
+        let array = to_half_list_array(&vecs);
+        assert_eq!(vecs, from_half_array(&array).unwrap());
+
+        let refs = half_refs_from_array(&array)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(vecs[0], refs[0].map(|v| v.to_half_vector()));
+    }
+
+    #[test]
+    fn test_half_array_float32() {
+        let array = FixedSizeListArray::from_iter_primitive::<Float32Type, _, _>(
+            vec![Some(vec![Some(1.0), Some(2.0)])],
+            2,
+        );
+        assert!(from_half_array(&array).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+// Conversions between Arrow arrays and vectors
+//
+// Rows are read lazily and vectors are borrowed from list arrays of primitive
+// values without copying, so batches can be streamed into a binary COPY.
+
+pub(crate) mod bit;
+pub(crate) mod sparsevec;
+pub(crate) mod vector;
+
+#[cfg(feature = "halfvec")]
+pub(crate) mod halfvec;
+
+use arrow_array::types::ArrowPrimitiveType;
+use arrow_array::{Array, FixedSizeListArray, LargeListArray, ListArray, PrimitiveArray};
+use arrow_buffer::{NullBuffer, OffsetBuffer};
+use arrow_schema::Field;
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::{limits, Error};
+
+// a row of a list array, with an error for null elements
+type Row<'a, T> = Result<Option<&'a [T]>, Error>;
+
+type Ranges<'a> = Box<dyn ExactSizeIterator<Item = Range<usize>> + 'a>;
+
+// returns an iterator over the rows of a list array of primitive values
+pub(crate) fn list_rows<'a, T: ArrowPrimitiveType>(
+    array: &'a dyn Array,
+    type_name: &'static str,
+) -> Result<impl ExactSizeIterator<Item = Row<'a, T::Native>> + 'a, Error> {
+    let any = array.as_any();
+    let (values, ranges): (_, Ranges<'a>) =
+        if let Some(a) = any.downcast_ref::<FixedSizeListArray>() {
+            let size = a.value_length() as usize;
+            let ranges = (0..a.len()).map(move |i| {
+                let start = a.value_offset(i) as usize;
+                start..start + size
+            });
+            (a.values(), Box::new(ranges))
+        } else if let Some(a) = any.downcast_ref::<ListArray>() {
+            let ranges = a
+                .value_offsets()
+                .windows(2)
+                .map(|w| w[0] as usize..w[1] as usize);
+            (a.values(), Box::new(ranges))
+        } else if let Some(a) = any.downcast_ref::<LargeListArray>() {
+            let ranges = a
+                .value_offsets()
+                .windows(2)
+                .map(|w| w[0] as usize..w[1] as usize);
+            (a.values(), Box::new(ranges))
+        } else {
+            return Err(unsupported(array, type_name));
+        };
+
+    let values = values
+        .as_any()
+        .downcast_ref::<PrimitiveArray<T>>()
+        .ok_or_else(|| unsupported(array, type_name))?;
+
+    Ok(ranges.enumerate().map(move |(i, range)| {
+        if array.is_null(i) {
+            return Ok(None);
+        }
+        if let Some(nulls) = values.nulls() {
+            if range.clone().any(|j| nulls.is_null(j)) {
+                return Err(Error::NullElement { type_name });
+            }
+        }
+        Ok(Some(&values.values()[range]))
+    }))
+}
+
+// creates a fixed-size list array, filling null rows with default values
+pub(crate) fn to_fixed_size_list<T: ArrowPrimitiveType>(
+    rows: &[Option<&[T::Native]>],
+    type_name: &'static str,
+) -> Result<FixedSizeListArray, Error> {
+    let size = limits::same_len(rows.iter().flatten().map(|v| v.len()), type_name)?.unwrap_or(0);
+
+    let mut values = Vec::with_capacity(rows.len() * size);
+    for row in rows {
+        match row {
+            Some(row) => values.extend_from_slice(row),
+            None => values.resize(values.len() + size, T::Native::default()),
+        }
+    }
+
+    let field = Arc::new(Field::new("item", T::DATA_TYPE, false));
+    let values = Arc::new(PrimitiveArray::<T>::from_iter_values(values));
+    // cannot fail since every row has the same size
+    Ok(
+        FixedSizeListArray::try_new(field, i32::try_from(size).unwrap(), values, nulls(rows))
+            .unwrap(),
+    )
+}
+
+// creates a list array
+pub(crate) fn to_list<T: ArrowPrimitiveType>(rows: &[Option<&[T::Native]>]) -> ListArray {
+    let offsets = OffsetBuffer::from_lengths(rows.iter().map(|v| v.map(|v| v.len()).unwrap_or(0)));
+    let values = rows.iter().flatten().flat_map(|v| v.iter().copied());
+    let field = Arc::new(Field::new("item", T::DATA_TYPE, false));
+    let values = Arc::new(PrimitiveArray::<T>::from_iter_values(values));
+    // cannot fail since the offsets match the values
+    ListArray::try_new(field, offsets, values, nulls(rows)).unwrap()
+}
+
+pub(crate) fn nulls<T>(rows: &[Option<T>]) -> Option<NullBuffer> {
+    if rows.iter().all(|v| v.is_some()) {
+        return None;
+    }
+    Some(NullBuffer::from(
+        rows.iter().map(|v| v.is_some()).collect::<Vec<bool>>(),
+    ))
+}
+
+pub(crate) fn unsupported(array: &dyn Array, type_name: &'static str) -> Error {
+    Error::UnsupportedDataType {
+        type_name,
+        data_type: array.data_type().to_string(),
+    }
+}
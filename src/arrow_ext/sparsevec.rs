@@ -0,0 +1,137 @@
+use arrow_array::types::{Float32Type, Int32Type};
+use arrow_array::{Array, ArrayRef, Int32Array, StructArray};
+use arrow_schema::{DataType, Field, Fields};
+use std::sync::Arc;
+
+use crate::{Error, SparseVector};
+
+/// Creates sparse vectors from a `StructArray` with `dim` (`Int32`), `indices` (list of `Int32`), and `values` (list of `Float32`) fields.
+///
+/// Indices start at 0 and null rows become `None`.
+pub fn from_sparse_array(array: &dyn Array) -> Result<Vec<Option<SparseVector>>, Error> {
+    iter_sparse_array(array)?.collect()
+}
+
+/// Returns an iterator that creates sparse vectors from a `StructArray` one row at a time.
+///
+/// The array has the same fields as with `from_sparse_array`.
+pub fn iter_sparse_array(
+    array: &dyn Array,
+) -> Result<impl ExactSizeIterator<Item = Result<Option<SparseVector>, Error>> + '_, Error> {
+    let unsupported = || super::unsupported(array, "sparsevec");
+    let array = array
+        .as_any()
+        .downcast_ref::<StructArray>()
+        .ok_or_else(unsupported)?;
+    let dims = array
+        .column_by_name("dim")
+        .and_then(|v| v.as_any().downcast_ref::<Int32Array>())
+        .ok_or_else(unsupported)?;
+    let indices = array.column_by_name("indices").ok_or_else(unsupported)?;
+    let indices = super::list_rows::<Int32Type>(indices.as_ref(), "sparsevec")?;
+    let values = array.column_by_name("values").ok_or_else(unsupported)?;
+    let values = super::list_rows::<Float32Type>(values.as_ref(), "sparsevec")?;
+
+    Ok(indices
+        .zip(values)
+        .enumerate()
+        .map(move |(i, (indices, values))| {
+            if array.is_null(i) {
+                return Ok(None);
+            }
+            match (dims.is_valid(i), indices?, values?) {
+                (true, Some(indices), Some(values)) => {
+                    SparseVector::try_new(dims.value(i), indices.to_vec(), values.to_vec())
+                        .map(Some)
+                }
+                _ => Err(Error::NullElement {
+                    type_name: "sparsevec",
+                }),
+            }
+        }))
+}
+
+/// Creates a `StructArray` with `dim`, `indices`, and `values` fields from sparse vectors.
+pub fn to_sparse_array(vecs: &[Option<SparseVector>]) -> StructArray {
+    let dims =
+        Int32Array::from_iter_values(vecs.iter().map(|v| v.as_ref().map(|v| v.dim).unwrap_or(0)));
+    // use empty lists for null rows
+    let indices = super::to_list::<Int32Type>(
+        &vecs
+            .iter()
+            .map(|v| Some(v.as_ref().map(|v| v.indices.as_slice()).unwrap_or(&[])))
+            .collect::<Vec<_>>(),
+    );
+    let values = super::to_list::<Float32Type>(
+        &vecs
+            .iter()
+            .map(|v| Some(v.as_ref().map(|v| v.values.as_slice()).unwrap_or(&[])))
+            .collect::<Vec<_>>(),
+    );
+
+    let fields = Fields::from(vec![
+        Field::new("dim", DataType::Int32, false),
+        Field::new("indices", indices.data_type().clone(), false),
+        Field::new("values", values.data_type().clone(), false),
+    ]);
+    let columns: Vec<ArrayRef> = vec![Arc::new(dims), Arc::new(indices), Arc::new(values)];
+    // cannot fail since the columns have the same length
+    StructArray::try_new(fields, columns, super::nulls(vecs)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arrow::{from_sparse_array, iter_sparse_array, to_sparse_array};
+    use crate::{Error, SparseVector};
+    use arrow_array::types::{Float32Type, Int32Type};
+    use arrow_array::{Array, ArrayRef, Int32Array, ListArray, StructArray};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_sparse_array() {
+        let vecs = vec![
+            Some(SparseVector::from_dense(&[1.0, 0.0, 2.0])),
+            None,
+            Some(SparseVector::from_dense(&[0.0, 0.0, 0.0, 3.0])),
+        ];
+        let array = to_sparse_array(&vecs);
+        assert_eq!(3, array.len());
+        assert!(array.is_null(1));
+        assert_eq!(vecs, from_sparse_array(&array).unwrap());
+
+        let mut iter = iter_sparse_array(&array).unwrap();
+        assert_eq!(3, iter.len());
+        assert_eq!(Some(Ok(vecs[0].clone())), iter.next());
+    }
+
+    #[test]
+    fn test_sparse_array_invalid() {
+        let dims: ArrayRef = Arc::new(Int32Array::from(vec![3]));
+        let indices: ArrayRef = Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(2), Some(0)]),
+        ]));
+        let values: ArrayRef = Arc::new(ListArray::from_iter_primitive::<Float32Type, _, _>(vec![
+            Some(vec![Some(1.0), Some(2.0)]),
+        ]));
+        let array = StructArray::try_from(vec![
+            ("dim", dims),
+            ("indices", indices),
+            ("values", values),
+        ])
+        .unwrap();
+        assert_eq!(Err(Error::UnsortedIndices), from_sparse_array(&array));
+        assert_eq!(
+            Some(Err(Error::UnsortedIndices)),
+            iter_sparse_array(&array).unwrap().next()
+        );
+
+        let array = Int32Array::from(vec![3]);
+        assert_eq!(
+            Err(Error::UnsupportedDataType {
+                type_name: "sparsevec",
+                data_type: "Int32".to_string()
+            }),
+            from_sparse_array(&array)
+        );
+    }
+}
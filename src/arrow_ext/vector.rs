@@ -0,0 +1,160 @@
+use arrow_array::types::Float32Type;
+use arrow_array::{Array, FixedSizeListArray, ListArray};
+
+use crate::{Error, Vector, VectorRef};
+
+/// Borrows vectors from a list array of `Float32` values, like a `FixedSizeListArray`.
+///
+/// Rows are read as the iterator advances and null rows become `None`.
+pub fn refs_from_array(
+    array: &dyn Array,
+) -> Result<impl ExactSizeIterator<Item = Result<Option<VectorRef<'_>>, Error>> + '_, Error> {
+    Ok(
+        super::list_rows::<Float32Type>(array, "vector")?
+            .map(|v| v.map(|v| v.map(VectorRef::from))),
+    )
+}
+
+/// Creates vectors from a list array of `Float32` values, like a `FixedSizeListArray`.
+///
+/// Null rows become `None`.
+pub fn from_array(array: &dyn Array) -> Result<Vec<Option<Vector>>, Error> {
+    super::list_rows::<Float32Type>(array, "vector")?
+        .map(|v| v.map(|v| v.map(|v| Vector::from(v.to_vec()))))
+        .collect()
+}
+
+/// Creates a `FixedSizeListArray` from vectors with the same number of dimensions.
+pub fn to_fixed_size_list_array(vecs: &[Option<Vector>]) -> Result<FixedSizeListArray, Error> {
+    super::to_fixed_size_list::<Float32Type>(&rows(vecs), "vector")
+}
+
+/// Creates a `ListArray` from vectors.
+pub fn to_list_array(vecs: &[Option<Vector>]) -> ListArray {
+    super::to_list::<Float32Type>(&rows(vecs))
+}
+
+fn rows(vecs: &[Option<Vector>]) -> Vec<Option<&[f32]>> {
+    vecs.iter()
+        .map(|v| v.as_ref().map(|v| v.as_slice()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arrow::{from_array, refs_from_array, to_fixed_size_list_array, to_list_array};
+    use crate::{Error, Vector};
+    use arrow_array::types::Float32Type;
+    use arrow_array::{Array, FixedSizeListArray, Int32Array, LargeListArray, ListArray};
+
+    #[test]
+    fn test_fixed_size_list() {
+        let vecs = vec![
+            Some(Vector::from(vec![1.0, 2.0])),
+            None,
+            Some(Vector::from(vec![3.0, 4.0])),
+        ];
+        let array = to_fixed_size_list_array(&vecs).unwrap();
+        assert_eq!(3, array.len());
+        assert_eq!(2, array.value_length());
+        assert!(array.is_null(1));
+        assert_eq!(vecs, from_array(&array).unwrap());
+
+        let array = FixedSizeListArray::from_iter_primitive::<Float32Type, _, _>(
+            vec![
+                Some(vec![Some(1.0), Some(2.0)]),
+                None,
+                Some(vec![Some(3.0), Some(4.0)]),
+            ],
+            2,
+        );
+        assert_eq!(vecs, from_array(&array).unwrap());
+        assert_eq!(vecs, from_array(&array.slice(0, 3)).unwrap());
+        assert_eq!(vecs[1..], from_array(&array.slice(1, 2)).unwrap());
+    }
+
+    #[test]
+    fn test_list() {
+        let vecs = vec![
+            Some(Vector::from(vec![1.0, 2.0])),
+            None,
+            Some(Vector::from(vec![3.0, 4.0])),
+        ];
+        let array = to_list_array(&vecs);
+        assert_eq!(3, array.len());
+        assert_eq!(vecs, from_array(&array).unwrap());
+        assert_eq!(vecs[2..], from_array(&array.slice(2, 1)).unwrap());
+
+        let array = LargeListArray::from_iter_primitive::<Float32Type, _, _>(vec![
+            Some(vec![Some(1.0), Some(2.0)]),
+            None,
+            Some(vec![Some(3.0), Some(4.0)]),
+        ]);
+        assert_eq!(vecs, from_array(&array).unwrap());
+    }
+
+    #[test]
+    fn test_refs() {
+        let vecs = vec![
+            Some(Vector::from(vec![1.0, 2.0])),
+            None,
+            Some(Vector::from(vec![3.0, 4.0])),
+        ];
+        let array = to_fixed_size_list_array(&vecs).unwrap();
+        let mut refs = refs_from_array(&array).unwrap();
+        assert_eq!(3, refs.len());
+        assert_eq!(
+            Some(Vector::from(vec![1.0, 2.0])),
+            refs.next().unwrap().unwrap().map(|v| v.to_vector())
+        );
+        assert!(refs.next().unwrap().unwrap().is_none());
+        assert_eq!(1, refs.len());
+
+        let array = ListArray::from_iter_primitive::<Float32Type, _, _>(vec![
+            Some(vec![Some(1.0)]),
+            Some(vec![None]),
+        ]);
+        let mut refs = refs_from_array(&array).unwrap();
+        assert!(refs.next().unwrap().is_ok());
+        assert_eq!(
+            Err(Error::NullElement {
+                type_name: "vector"
+            }),
+            refs.next().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        let vecs = vec![
+            Some(Vector::from(vec![1.0, 2.0])),
+            Some(Vector::from(vec![3.0])),
+        ];
+        assert_eq!(
+            Err(Error::DifferentDimensions {
+                type_name: "vector",
+                left: 2,
+                right: 1
+            }),
+            to_fixed_size_list_array(&vecs)
+        );
+
+        let array = Int32Array::from(vec![1, 2]);
+        assert_eq!(
+            Err(Error::UnsupportedDataType {
+                type_name: "vector",
+                data_type: "Int32".to_string()
+            }),
+            from_array(&array)
+        );
+
+        let array =
+            ListArray::from_iter_primitive::<Float32Type, _, _>(vec![Some(vec![Some(1.0), None])]);
+        assert_eq!(
+            Err(Error::NullElement {
+                type_name: "vector"
+            }),
+            from_array(&array)
+        );
+    }
+}
@@ -1,13 +1,38 @@
 #[cfg(feature = "diesel")]
-use crate::diesel_ext::bit::BitType;
+use crate::diesel_ext::bit::{BitType, VarBitType};
 
 #[cfg(feature = "diesel")]
 use diesel::{deserialize::FromSqlRow, expression::AsExpression};
 
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use std::fmt;
+use std::ops::Not;
+use std::str::FromStr;
+
+use crate::distance;
+use crate::ops;
+use crate::Error;
+
+// the maximum length of a bit string in Postgres
+pub(crate) const BIT_MAX_LEN: usize = i32::MAX as usize - 7;
+
+fn check_max_len(len: usize) -> Result<(), Error> {
+    if len > BIT_MAX_LEN {
+        return Err(Error::TooManyDimensions {
+            type_name: "bit",
+            max: BIT_MAX_LEN,
+        });
+    }
+    Ok(())
+}
+
 /// A bit string.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "diesel", derive(FromSqlRow, AsExpression))]
 #[cfg_attr(feature = "diesel", diesel(sql_type = BitType))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = VarBitType))]
 pub struct Bit {
     pub(crate) len: usize,
     pub(crate) data: Vec<u8>,
@@ -16,10 +41,20 @@ pub struct Bit {
 impl Bit {
     /// Creates a bit string from a slice of bits.
     pub fn new(data: &[bool]) -> Bit {
-        let len = data.len();
+        Bit::from_bits(data.iter().copied())
+    }
+
+    /// Creates a bit string from a slice of bits, checking the same limits as the server.
+    pub fn try_new(data: &[bool]) -> Result<Bit, Error> {
+        check_max_len(data.len())?;
+        Ok(Bit::new(data))
+    }
+
+    pub(crate) fn from_bits<I: ExactSizeIterator<Item = bool>>(bits: I) -> Bit {
+        let len = bits.len();
         let mut bytes = vec![0; (len + 7) / 8];
-        for (i, v) in data.iter().enumerate() {
-            bytes[i / 8] |= u8::from(*v) << (7 - (i % 8));
+        for (i, v) in bits.enumerate() {
+            bytes[i / 8] |= u8::from(v) << (7 - (i % 8));
         }
         Bit { len, data: bytes }
     }
@@ -32,6 +67,26 @@ impl Bit {
         }
     }
 
+    /// Creates a bit string from a slice of bytes and a number of bits.
+    ///
+    /// Bits after `len` are ignored.
+    pub fn from_bytes_with_len(data: &[u8], len: usize) -> Result<Bit, Error> {
+        let expected = len / 8 + usize::from(len % 8 != 0);
+        if data.len() != expected {
+            return Err(Error::InvalidLength {
+                type_name: "bit",
+                expected,
+                actual: data.len(),
+            });
+        }
+        let mut bit = Bit {
+            len,
+            data: data.to_vec(),
+        };
+        bit.zero_padding();
+        Ok(bit)
+    }
+
     /// Returns the number of bits in the bit string.
     pub fn len(&self) -> usize {
         self.len
@@ -47,27 +102,264 @@ impl Bit {
         self.data.as_slice()
     }
 
+    /// Returns the bit at an index, or `None` if out of bounds.
+    pub fn get(&self, index: usize) -> Option<bool> {
+        if index < self.len {
+            Some(self.bit(index))
+        } else {
+            None
+        }
+    }
+
+    /// Sets the bit at an index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn set(&mut self, index: usize, value: bool) {
+        assert!(
+            index < self.len,
+            "index out of bounds: the len is {} but the index is {}",
+            self.len,
+            index
+        );
+        let mask = 1 << (7 - (index % 8));
+        if value {
+            self.data[index / 8] |= mask;
+        } else {
+            self.data[index / 8] &= !mask;
+        }
+    }
+
+    /// Returns an iterator over the bits.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = bool> + '_ {
+        (0..self.len).map(move |i| self.bit(i))
+    }
+
+    /// Returns the number of set bits.
+    pub fn count_ones(&self) -> usize {
+        self.data.iter().map(|v| v.count_ones() as usize).sum()
+    }
+
+    /// Concatenates with another bit string, like the `||` operator.
+    pub fn concat(&self, other: &Bit) -> Bit {
+        Bit::from_bits((0..self.len + other.len).map(|i| {
+            if i < self.len {
+                self.bit(i)
+            } else {
+                other.bit(i - self.len)
+            }
+        }))
+    }
+
+    /// Returns `count` bits starting at the 1-based position `start`, like the `substring` function.
+    pub fn substring(&self, start: i32, count: i32) -> Result<Bit, Error> {
+        if count < 0 {
+            return Err(Error::NegativeSubstringLength);
+        }
+
+        // cannot overflow
+        let end = (i64::from(start) + i64::from(count)).min(self.len as i64 + 1);
+        let start = i64::from(start).max(1);
+        if end <= start {
+            return Ok(Bit::new(&[]));
+        }
+
+        let start = (start - 1) as usize;
+        let end = (end - 1) as usize;
+        Ok(Bit::from_bits((start..end).map(|i| self.bit(i))))
+    }
+
+    /// Returns the Hamming distance to another bit string, like the `<~>` operator.
+    pub fn hamming_distance(&self, other: &Bit) -> Result<f64, Error> {
+        self.check_len(other)?;
+        // lossless for any bit string Postgres can store
+        Ok(distance::hamming_distance(&self.data, &other.data) as f64)
+    }
+
+    /// Returns the Jaccard distance to another bit string, like the `<%>` operator.
+    pub fn jaccard_distance(&self, other: &Bit) -> Result<f64, Error> {
+        self.check_len(other)?;
+        let (ab, aa, bb) = distance::jaccard_counts(&self.data, &other.data);
+        if ab == 0 {
+            Ok(1.0)
+        } else {
+            Ok(1.0 - (ab as f64 / (aa + bb - ab) as f64))
+        }
+    }
+
+    fn try_bitand(&self, other: &Bit) -> Result<Bit, Error> {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    fn try_bitor(&self, other: &Bit) -> Result<Bit, Error> {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    fn try_bitxor(&self, other: &Bit) -> Result<Bit, Error> {
+        self.zip_with(other, |a, b| a ^ b)
+    }
+
+    fn zip_with<F: Fn(u8, u8) -> u8>(&self, other: &Bit, f: F) -> Result<Bit, Error> {
+        self.check_len(other)?;
+        let data = self
+            .data
+            .iter()
+            .zip(&other.data)
+            .map(|(a, b)| f(*a, *b))
+            .collect();
+        Ok(Bit {
+            len: self.len,
+            data,
+        })
+    }
+
+    fn bit(&self, index: usize) -> bool {
+        (self.data[index / 8] >> (7 - (index % 8))) & 1 == 1
+    }
+
+    // zeros the bits after the length like Postgres
+    fn zero_padding(&mut self) {
+        if self.len % 8 != 0 {
+            if let Some(last) = self.data.last_mut() {
+                *last &= 0xFF << (8 - self.len % 8);
+            }
+        }
+    }
+
+    fn check_len(&self, other: &Bit) -> Result<(), Error> {
+        if self.len != other.len {
+            return Err(Error::DifferentDimensions {
+                type_name: "bit",
+                left: self.len,
+                right: other.len,
+            });
+        }
+        Ok(())
+    }
+
     #[cfg(any(feature = "postgres", feature = "sqlx", feature = "diesel"))]
-    pub(crate) fn from_sql(buf: &[u8]) -> Result<Bit, Box<dyn std::error::Error + Sync + Send>> {
+    pub(crate) fn from_sql(buf: &[u8]) -> Result<Bit, Error> {
         if buf.len() < 4 {
-            return Err("invalid length".into());
+            return Err(Error::Truncated {
+                type_name: "bit",
+                expected: 4,
+                actual: buf.len(),
+            });
         }
 
-        let len = i32::from_be_bytes(buf[0..4].try_into()?).try_into()?;
+        let len = i32::from_be_bytes([buf[0], buf[1], buf[2], buf[3]]);
+        let len = usize::try_from(len).map_err(|_| Error::DimensionOverflow {
+            type_name: "bit",
+            value: len.into(),
+        })?;
 
-        if buf.len() - 4 != len / 8 + usize::from(len % 8 != 0) {
-            return Err("invalid length".into());
+        let expected = 4 + len / 8 + usize::from(len % 8 != 0);
+        if buf.len() != expected {
+            return Err(Error::InvalidLength {
+                type_name: "bit",
+                expected,
+                actual: buf.len(),
+            });
         }
 
-        let data = buf[4..4 + (len + 7) / 8].to_vec();
+        let mut bit = Bit {
+            len,
+            data: buf[4..].to_vec(),
+        };
+        bit.zero_padding();
+        Ok(bit)
+    }
+}
+
+ops::impl_op!(Bit, BitAnd, bitand, BitAndAssign, bitand_assign, try_bitand);
+ops::impl_op!(Bit, BitOr, bitor, BitOrAssign, bitor_assign, try_bitor);
+ops::impl_op!(Bit, BitXor, bitxor, BitXorAssign, bitxor_assign, try_bitxor);
 
-        Ok(Bit { len, data })
+impl Not for &Bit {
+    type Output = Bit;
+
+    fn not(self) -> Bit {
+        let mut bit = Bit {
+            len: self.len,
+            data: self.data.iter().map(|v| !v).collect(),
+        };
+        bit.zero_padding();
+        bit
+    }
+}
+
+impl Not for Bit {
+    type Output = Bit;
+
+    fn not(self) -> Bit {
+        !&self
+    }
+}
+
+impl fmt::Display for Bit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for bit in self.iter() {
+            f.write_str(if bit { "1" } else { "0" })?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Bit {
+    type Err = Error;
+
+    // follows bit_in in Postgres, including the optional B and X prefixes
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (hex, digits) = if let Some(digits) = s.strip_prefix(&['x', 'X'][..]) {
+            (true, digits)
+        } else {
+            (false, s.strip_prefix(&['b', 'B'][..]).unwrap_or(s))
+        };
+
+        if hex {
+            let len = digits.len() * 4;
+            let mut data = vec![0; (len + 7) / 8];
+            for (i, c) in digits.chars().enumerate() {
+                let v = c.to_digit(16).ok_or(Error::InvalidHexDigit(c))? as u8;
+                data[i / 2] |= if i % 2 == 0 { v << 4 } else { v };
+            }
+            Ok(Bit { len, data })
+        } else {
+            let len = digits.len();
+            let mut data = vec![0; (len + 7) / 8];
+            for (i, c) in digits.chars().enumerate() {
+                match c {
+                    '0' => {}
+                    '1' => data[i / 8] |= 1 << (7 - (i % 8)),
+                    _ => return Err(Error::InvalidBinaryDigit(c)),
+                }
+            }
+            Ok(Bit { len, data })
+        }
+    }
+}
+
+// serializes as a bit string like the text representation
+#[cfg(feature = "serde")]
+impl Serialize for Bit {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Bit {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::Bit;
+    use super::check_max_len;
+    use crate::{Bit, Error};
 
     #[test]
     fn test_from_bytes() {
@@ -89,4 +381,184 @@ mod tests {
         assert_eq!(0, vec.len());
         assert!(vec.is_empty());
     }
+
+    #[test]
+    fn test_display() {
+        let vec = Bit::new(&[false, true, false, true, false, false, false, false, true]);
+        assert_eq!("010100001", vec.to_string());
+        assert_eq!("", Bit::new(&[]).to_string());
+    }
+
+    #[test]
+    fn test_from_str() {
+        let vec: Bit = "010100001".parse().unwrap();
+        assert_eq!(
+            Bit::new(&[false, true, false, true, false, false, false, false, true]),
+            vec
+        );
+
+        let vec: Bit = "B101".parse().unwrap();
+        assert_eq!(Bit::new(&[true, false, true]), vec);
+
+        let vec: Bit = "x0fA".parse().unwrap();
+        assert_eq!(12, vec.len());
+        assert_eq!(&[0b00001111, 0b10100000], vec.as_bytes());
+
+        let vec: Bit = "".parse().unwrap();
+        assert!(vec.is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let vec = Bit::new(&[true, false, true]);
+        let json = serde_json::to_string(&vec).unwrap();
+        assert_eq!(r#""101""#, json);
+        assert_eq!(vec, serde_json::from_str(&json).unwrap());
+
+        let err = serde_json::from_str::<Bit>(r#""102""#).unwrap_err();
+        assert_eq!("\"2\" is not a valid binary digit", err.to_string());
+    }
+
+    #[test]
+    fn test_from_str_invalid() {
+        assert_eq!(Err(Error::InvalidBinaryDigit('2')), "0102".parse::<Bit>());
+        assert_eq!(Err(Error::InvalidBinaryDigit(' ')), " 01".parse::<Bit>());
+        assert_eq!(Err(Error::InvalidHexDigit('g')), "xfg".parse::<Bit>());
+    }
+
+    #[test]
+    fn test_hamming_distance() {
+        let a = Bit::new(&[false, true, false, true, false, false, false, false, true]);
+        let b = Bit::new(&[false, true, true, true, false, false, false, false, true]);
+        assert_eq!(1.0, a.hamming_distance(&b).unwrap());
+        assert_eq!(0.0, a.hamming_distance(&a).unwrap());
+    }
+
+    #[test]
+    fn test_jaccard_distance() {
+        let a = Bit::new(&[false, true, false, true, false, false, false, false, true]);
+        let b = Bit::new(&[false, true, true, true, false, false, false, false, true]);
+        assert_eq!(0.25, a.jaccard_distance(&b).unwrap());
+
+        let zero = Bit::new(&[false; 9]);
+        assert_eq!(1.0, zero.jaccard_distance(&zero).unwrap());
+    }
+
+    #[test]
+    fn test_distance_different_lengths() {
+        let a = Bit::new(&[true, false]);
+        let b = Bit::new(&[true, false, true]);
+        let err = a.hamming_distance(&b).unwrap_err();
+        assert_eq!("different bit lengths 2 and 3", err.to_string());
+    }
+
+    #[test]
+    fn test_try_new() {
+        let vec = Bit::try_new(&[true, false, true]).unwrap();
+        assert_eq!(Bit::new(&[true, false, true]), vec);
+
+        // too large to allocate, so test the check directly
+        assert_eq!(Ok(()), check_max_len(2147483640));
+        let err = check_max_len(2147483641).unwrap_err();
+        assert_eq!(
+            Error::TooManyDimensions {
+                type_name: "bit",
+                max: 2147483640
+            },
+            err
+        );
+        assert_eq!(
+            "bit string length exceeds the maximum allowed (2147483640)",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_with_len() {
+        let vec = Bit::from_bytes_with_len(&[0b11111111, 0b11111111], 10).unwrap();
+        assert_eq!(10, vec.len());
+        assert_eq!(&[0b11111111, 0b11000000], vec.as_bytes());
+        assert_eq!(
+            Err(Error::InvalidLength {
+                type_name: "bit",
+                expected: 1,
+                actual: 2
+            }),
+            Bit::from_bytes_with_len(&[0, 0], 8)
+        );
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut vec = Bit::new(&[true, false, true]);
+        assert_eq!(Some(true), vec.get(0));
+        assert_eq!(Some(false), vec.get(1));
+        assert_eq!(None, vec.get(3));
+
+        vec.set(0, false);
+        vec.set(1, true);
+        assert_eq!("011", vec.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+    fn test_set_out_of_bounds() {
+        Bit::new(&[true, false, true]).set(3, true);
+    }
+
+    #[test]
+    fn test_iter() {
+        let vec = Bit::new(&[true, false, true]);
+        assert_eq!(vec![true, false, true], vec.iter().collect::<Vec<bool>>());
+        assert_eq!(2, vec.count_ones());
+    }
+
+    #[test]
+    fn test_ops() {
+        let a: Bit = "1100".parse().unwrap();
+        let b: Bit = "1010".parse().unwrap();
+        assert_eq!("1000", (&a & &b).unwrap().to_string());
+        assert_eq!("1110", (&a | &b).unwrap().to_string());
+        assert_eq!("0110", (&a ^ &b).unwrap().to_string());
+        assert_eq!("0011", (!&a).to_string());
+        assert_eq!(&[0b00110000], (!a).as_bytes());
+
+        let c = Bit::new(&[true]);
+        assert_eq!(
+            Err(Error::DifferentDimensions {
+                type_name: "bit",
+                left: 4,
+                right: 1
+            }),
+            &b & &c
+        );
+    }
+
+    #[test]
+    fn test_assign_ops() {
+        let mut a: Bit = "1100".parse().unwrap();
+        a ^= "1010".parse::<Bit>().unwrap();
+        assert_eq!("0110", a.to_string());
+    }
+
+    #[test]
+    fn test_concat() {
+        let a: Bit = "101".parse().unwrap();
+        let b: Bit = "0011".parse().unwrap();
+        assert_eq!("1010011", a.concat(&b).to_string());
+    }
+
+    #[test]
+    fn test_substring() {
+        let vec: Bit = "10110".parse().unwrap();
+        assert_eq!("011", vec.substring(2, 3).unwrap().to_string());
+        assert_eq!("1", vec.substring(0, 2).unwrap().to_string());
+        assert_eq!("10", vec.substring(4, 10).unwrap().to_string());
+        assert_eq!("", vec.substring(6, 1).unwrap().to_string());
+        assert_eq!("", vec.substring(-5, 2).unwrap().to_string());
+        assert_eq!("0110", vec.substring(2, i32::MAX).unwrap().to_string());
+        assert_eq!("", vec.substring(i32::MIN, i32::MAX).unwrap().to_string());
+        assert_eq!(Err(Error::NegativeSubstringLength), vec.substring(1, -1));
+    }
 }
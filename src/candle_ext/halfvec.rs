@@ -0,0 +1,90 @@
+use candle_core::{Device, Tensor};
+use half::f16;
+
+use crate::HalfVector;
+
+// uses the F16 dtype directly to avoid rounding through f32
+impl TryFrom<&Tensor> for HalfVector {
+    type Error = candle_core::Error;
+
+    fn try_from(tensor: &Tensor) -> Result<Self, Self::Error> {
+        HalfVector::try_new(tensor.to_vec1::<f16>()?).map_err(candle_core::Error::wrap)
+    }
+}
+
+impl TryFrom<Tensor> for HalfVector {
+    type Error = candle_core::Error;
+
+    fn try_from(tensor: Tensor) -> Result<Self, Self::Error> {
+        HalfVector::try_from(&tensor)
+    }
+}
+
+impl TryFrom<&HalfVector> for Tensor {
+    type Error = candle_core::Error;
+
+    fn try_from(vec: &HalfVector) -> Result<Self, Self::Error> {
+        Tensor::new(vec.as_slice(), &Device::Cpu)
+    }
+}
+
+/// Splits a 2-D `F16` tensor into half vectors, one per row.
+pub fn from_half_tensor(tensor: &Tensor) -> candle_core::Result<Vec<HalfVector>> {
+    tensor
+        .to_vec2::<f16>()?
+        .into_iter()
+        .map(|v| HalfVector::try_new(v).map_err(candle_core::Error::wrap))
+        .collect()
+}
+
+/// Stacks half vectors into a 2-D tensor on the CPU.
+pub fn to_half_tensor(vecs: &[HalfVector]) -> candle_core::Result<Tensor> {
+    super::stack(vecs.iter().map(|v| v.as_slice()), "halfvec")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::HalfVector;
+    use candle_core::{DType, Device, Tensor};
+    use half::f16;
+
+    #[test]
+    fn test_tensor() {
+        let tensor = Tensor::new(&[1.0f32, 2.0, 3.0], &Device::Cpu)
+            .unwrap()
+            .to_dtype(DType::F16)
+            .unwrap();
+        let vec = HalfVector::try_from(&tensor).unwrap();
+        assert_eq!(HalfVector::from_f32_slice(&[1.0, 2.0, 3.0]), vec);
+
+        let tensor = Tensor::try_from(&vec).unwrap();
+        assert_eq!(DType::F16, tensor.dtype());
+        assert_eq!(vec.to_vec(), tensor.to_vec1::<f16>().unwrap());
+
+        let tensor = Tensor::new(&[1.0f32, 2.0, 3.0], &Device::Cpu).unwrap();
+        assert!(HalfVector::try_from(tensor).is_err());
+
+        let tensor = Tensor::zeros(0, DType::F16, &Device::Cpu).unwrap();
+        let err = HalfVector::try_from(&tensor).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("halfvec must have at least 1 dimension"));
+
+        let tensor = Tensor::new(&[f16::ONE, f16::INFINITY], &Device::Cpu).unwrap();
+        let err = HalfVector::try_from(&tensor).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("infinite value not allowed in halfvec"));
+    }
+
+    #[test]
+    fn test_batch() {
+        let vecs = vec![
+            HalfVector::from_f32_slice(&[1.0, 2.0]),
+            HalfVector::from_f32_slice(&[3.0, 4.0]),
+        ];
+        let tensor = crate::candle::to_half_tensor(&vecs).unwrap();
+        assert_eq!((2, 2), tensor.dims2().unwrap());
+        assert_eq!(vecs, crate::candle::from_half_tensor(&tensor).unwrap());
+    }
+}
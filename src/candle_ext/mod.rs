@@ -0,0 +1,21 @@
+pub(crate) mod vector;
+
+#[cfg(feature = "halfvec")]
+pub(crate) mod halfvec;
+
+use candle_core::{Device, Tensor, WithDType};
+
+use crate::limits;
+
+// stacks rows of the same length into a 2-D tensor on the CPU
+fn stack<'a, T: WithDType>(
+    rows: impl ExactSizeIterator<Item = &'a [T]> + Clone,
+    type_name: &'static str,
+) -> candle_core::Result<Tensor> {
+    let nrows = rows.len();
+    let ncols = limits::same_len(rows.clone().map(|v| v.len()), type_name)
+        .map_err(candle_core::Error::wrap)?
+        .unwrap_or(0);
+    let data = rows.flat_map(|v| v.iter().copied()).collect();
+    Tensor::from_vec(data, (nrows, ncols), &Device::Cpu)
+}
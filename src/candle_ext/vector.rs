@@ -0,0 +1,116 @@
+use candle_core::{Device, Tensor};
+
+use crate::Vector;
+
+impl TryFrom<&Tensor> for Vector {
+    type Error = candle_core::Error;
+
+    fn try_from(tensor: &Tensor) -> Result<Self, Self::Error> {
+        Vector::try_new(tensor.to_vec1::<f32>()?).map_err(candle_core::Error::wrap)
+    }
+}
+
+impl TryFrom<Tensor> for Vector {
+    type Error = candle_core::Error;
+
+    fn try_from(tensor: Tensor) -> Result<Self, Self::Error> {
+        Vector::try_from(&tensor)
+    }
+}
+
+impl TryFrom<&Vector> for Tensor {
+    type Error = candle_core::Error;
+
+    fn try_from(vec: &Vector) -> Result<Self, Self::Error> {
+        Tensor::new(vec.as_slice(), &Device::Cpu)
+    }
+}
+
+/// Splits a 2-D `F32` tensor into vectors, one per row.
+pub fn from_tensor(tensor: &Tensor) -> candle_core::Result<Vec<Vector>> {
+    tensor
+        .to_vec2::<f32>()?
+        .into_iter()
+        .map(|v| Vector::try_new(v).map_err(candle_core::Error::wrap))
+        .collect()
+}
+
+/// Stacks vectors into a 2-D tensor on the CPU.
+pub fn to_tensor(vecs: &[Vector]) -> candle_core::Result<Tensor> {
+    super::stack(vecs.iter().map(|v| v.as_slice()), "vector")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Vector;
+    use candle_core::{DType, Device, Tensor};
+
+    #[test]
+    fn test_tensor() {
+        let tensor = Tensor::new(&[1.0f32, 2.0, 3.0], &Device::Cpu).unwrap();
+        let vec = Vector::try_from(&tensor).unwrap();
+        assert_eq!(Vector::from(vec![1.0, 2.0, 3.0]), vec);
+
+        let tensor = Tensor::try_from(&vec).unwrap();
+        assert_eq!(vec![1.0, 2.0, 3.0], tensor.to_vec1::<f32>().unwrap());
+    }
+
+    #[test]
+    fn test_tensor_invalid() {
+        let tensor = Tensor::new(&[1.0f64, 2.0, 3.0], &Device::Cpu).unwrap();
+        assert!(Vector::try_from(&tensor).is_err());
+
+        let tensor = Tensor::zeros((2, 3), DType::F32, &Device::Cpu).unwrap();
+        assert!(Vector::try_from(tensor).is_err());
+
+        let tensor = Tensor::zeros(0, DType::F32, &Device::Cpu).unwrap();
+        let err = Vector::try_from(&tensor).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("vector must have at least 1 dimension"));
+
+        let tensor = Tensor::zeros(16001, DType::F32, &Device::Cpu).unwrap();
+        let err = Vector::try_from(&tensor).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("vector cannot have more than 16000 dimensions"));
+
+        let tensor = Tensor::new(&[1.0f32, f32::NAN], &Device::Cpu).unwrap();
+        let err = Vector::try_from(&tensor).unwrap_err();
+        assert!(err.to_string().starts_with("NaN not allowed in vector"));
+    }
+
+    #[test]
+    fn test_from_tensor() {
+        let tensor = Tensor::new(&[[1.0f32, 2.0], [3.0, 4.0]], &Device::Cpu).unwrap();
+        let vecs = crate::candle::from_tensor(&tensor).unwrap();
+        assert_eq!(
+            vec![Vector::from(vec![1.0, 2.0]), Vector::from(vec![3.0, 4.0])],
+            vecs
+        );
+
+        let tensor = Tensor::new(&[1.0f32, 2.0], &Device::Cpu).unwrap();
+        assert!(crate::candle::from_tensor(&tensor).is_err());
+    }
+
+    #[test]
+    fn test_to_tensor() {
+        let vecs = vec![Vector::from(vec![1.0, 2.0]), Vector::from(vec![3.0, 4.0])];
+        let tensor = crate::candle::to_tensor(&vecs).unwrap();
+        assert_eq!((2, 2), tensor.dims2().unwrap());
+        assert_eq!(
+            vec![vec![1.0, 2.0], vec![3.0, 4.0]],
+            tensor.to_vec2::<f32>().unwrap()
+        );
+
+        let tensor = crate::candle::to_tensor(&[]).unwrap();
+        assert_eq!((0, 0), tensor.dims2().unwrap());
+
+        let vecs = vec![Vector::from(vec![1.0, 2.0]), Vector::from(vec![3.0])];
+        let err = crate::candle::to_tensor(&vecs).unwrap_err();
+        // candle appends a backtrace when enabled
+        assert!(err
+            .to_string()
+            .starts_with("different vector dimensions 2 and 1"));
+    }
+}
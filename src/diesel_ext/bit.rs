@@ -12,6 +12,10 @@ use crate::Bit;
 #[diesel(postgres_type(name = "bit"))]
 pub struct BitType;
 
+#[derive(SqlType, QueryId)]
+#[diesel(postgres_type(name = "varbit"))]
+pub struct VarBitType;
+
 impl ToSql<BitType, Pg> for Bit {
     fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
         let len = self.len;
@@ -23,7 +27,19 @@ impl ToSql<BitType, Pg> for Bit {
 
 impl FromSql<BitType, Pg> for Bit {
     fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
-        Bit::from_sql(value.as_bytes())
+        Ok(Bit::from_sql(value.as_bytes())?)
+    }
+}
+
+impl ToSql<VarBitType, Pg> for Bit {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        ToSql::<BitType, Pg>::to_sql(self, out)
+    }
+}
+
+impl FromSql<VarBitType, Pg> for Bit {
+    fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
+        FromSql::<BitType, Pg>::from_sql(value)
     }
 }
 
@@ -128,6 +144,19 @@ mod tests {
             .load::<Option<f64>>(&mut conn)?;
         assert_eq!(vec![Some(2.0), Some(0.0), Some(1.0), None], distances);
 
+        let varbit = diesel::select(diesel::dsl::sql::<crate::sql_types::VarBit>(
+            "B'101'::varbit",
+        ))
+        .get_result::<Bit>(&mut conn)?;
+        assert_eq!(Bit::new(&[true, false, true]), varbit);
+
+        let distance = diesel::select(
+            diesel::dsl::sql::<crate::sql_types::VarBit>("B'101'::varbit")
+                .hamming_distance(Bit::new(&[true, true, true])),
+        )
+        .get_result::<f64>(&mut conn)?;
+        assert_eq!(1.0, distance);
+
         Ok(())
     }
 }
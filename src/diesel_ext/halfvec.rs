@@ -6,7 +6,7 @@ use diesel::sql_types::SqlType;
 use std::convert::TryFrom;
 use std::io::Write;
 
-use crate::HalfVector;
+use crate::{FixedHalfVector, HalfVector, HalfVectorRef};
 
 #[derive(SqlType, QueryId)]
 #[diesel(postgres_type(name = "halfvec"))]
@@ -14,11 +14,26 @@ pub struct HalfVectorType;
 
 impl ToSql<HalfVectorType, Pg> for HalfVector {
     fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
-        let dim = self.0.len();
+        ToSql::<HalfVectorType, Pg>::to_sql(
+            &HalfVectorRef::from(self.as_slice()),
+            &mut out.reborrow(),
+        )
+    }
+}
+
+impl FromSql<HalfVectorType, Pg> for HalfVector {
+    fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
+        Ok(HalfVector::from_sql(value.as_bytes())?)
+    }
+}
+
+impl ToSql<HalfVectorType, Pg> for HalfVectorRef<'_> {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        let dim = self.len();
         out.write_all(&u16::try_from(dim)?.to_be_bytes())?;
         out.write_all(&0_u16.to_be_bytes())?;
 
-        for v in &self.0 {
+        for v in self.iter() {
             out.write_all(&v.to_be_bytes())?;
         }
 
@@ -26,9 +41,18 @@ impl ToSql<HalfVectorType, Pg> for HalfVector {
     }
 }
 
-impl FromSql<HalfVectorType, Pg> for HalfVector {
+impl<const N: usize> ToSql<HalfVectorType, Pg> for FixedHalfVector<N> {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        ToSql::<HalfVectorType, Pg>::to_sql(
+            &HalfVectorRef::from(self.as_slice()),
+            &mut out.reborrow(),
+        )
+    }
+}
+
+impl<const N: usize> FromSql<HalfVectorType, Pg> for FixedHalfVector<N> {
     fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
-        HalfVector::from_sql(value.as_bytes())
+        Ok(FixedHalfVector::from_sql(value.as_bytes())?)
     }
 }
 
@@ -34,7 +34,7 @@ impl ToSql<SparseVectorType, Pg> for SparseVector {
 
 impl FromSql<SparseVectorType, Pg> for SparseVector {
     fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
-        SparseVector::from_sql(value.as_bytes())
+        Ok(SparseVector::from_sql(value.as_bytes())?)
     }
 }
 
@@ -6,7 +6,7 @@ use diesel::sql_types::SqlType;
 use std::convert::TryFrom;
 use std::io::Write;
 
-use crate::Vector;
+use crate::{FixedVector, Vector, VectorRef};
 
 #[derive(SqlType, QueryId)]
 #[diesel(postgres_type(name = "vector"))]
@@ -14,11 +14,23 @@ pub struct VectorType;
 
 impl ToSql<VectorType, Pg> for Vector {
     fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
-        let dim = self.0.len();
+        ToSql::<VectorType, Pg>::to_sql(&VectorRef::from(self.as_slice()), &mut out.reborrow())
+    }
+}
+
+impl FromSql<VectorType, Pg> for Vector {
+    fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
+        Ok(Vector::from_sql(value.as_bytes())?)
+    }
+}
+
+impl ToSql<VectorType, Pg> for VectorRef<'_> {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        let dim = self.len();
         out.write_all(&u16::try_from(dim)?.to_be_bytes())?;
         out.write_all(&0_u16.to_be_bytes())?;
 
-        for v in &self.0 {
+        for v in self.iter() {
             out.write_all(&v.to_be_bytes())?;
         }
 
@@ -26,9 +38,15 @@ impl ToSql<VectorType, Pg> for Vector {
     }
 }
 
-impl FromSql<VectorType, Pg> for Vector {
+impl<const N: usize> ToSql<VectorType, Pg> for FixedVector<N> {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        ToSql::<VectorType, Pg>::to_sql(&VectorRef::from(self.as_slice()), &mut out.reborrow())
+    }
+}
+
+impl<const N: usize> FromSql<VectorType, Pg> for FixedVector<N> {
     fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
-        Vector::from_sql(value.as_bytes())
+        Ok(FixedVector::from_sql(value.as_bytes())?)
     }
 }
 
@@ -0,0 +1,384 @@
+// NEON kernels with runtime detection
+
+use std::arch::aarch64::*;
+use std::arch::is_aarch64_feature_detected;
+
+#[cfg(feature = "halfvec")]
+use half::{f16, slice::HalfFloatSliceExt};
+
+fn has_neon() -> bool {
+    is_aarch64_feature_detected!("neon")
+}
+
+pub(crate) fn l2_squared_distance(a: &[f32], b: &[f32]) -> Option<f32> {
+    if has_neon() {
+        return Some(unsafe { l2_squared_distance_neon(a, b) });
+    }
+    None
+}
+
+pub(crate) fn inner_product(a: &[f32], b: &[f32]) -> Option<f32> {
+    if has_neon() {
+        return Some(unsafe { inner_product_neon(a, b) });
+    }
+    None
+}
+
+pub(crate) fn cosine_similarity(a: &[f32], b: &[f32]) -> Option<f64> {
+    if has_neon() {
+        return Some(unsafe { cosine_similarity_neon(a, b) });
+    }
+    None
+}
+
+pub(crate) fn l1_distance(a: &[f32], b: &[f32]) -> Option<f32> {
+    if has_neon() {
+        return Some(unsafe { l1_distance_neon(a, b) });
+    }
+    None
+}
+
+#[cfg(feature = "halfvec")]
+pub(crate) fn half_l2_squared_distance(a: &[f16], b: &[f16]) -> Option<f32> {
+    if has_neon() {
+        return Some(half_chunks(a, b, 0.0, |distance, x, y| unsafe {
+            distance + l2_squared_distance_neon(x, y)
+        }));
+    }
+    None
+}
+
+#[cfg(feature = "halfvec")]
+pub(crate) fn half_inner_product(a: &[f16], b: &[f16]) -> Option<f32> {
+    if has_neon() {
+        return Some(half_chunks(a, b, 0.0, |distance, x, y| unsafe {
+            distance + inner_product_neon(x, y)
+        }));
+    }
+    None
+}
+
+#[cfg(feature = "halfvec")]
+pub(crate) fn half_cosine_similarity(a: &[f16], b: &[f16]) -> Option<f64> {
+    if has_neon() {
+        let (similarity, norma, normb) = half_chunks(a, b, (0.0, 0.0, 0.0), |sums, x, y| unsafe {
+            let (similarity, norma, normb) = cosine_sums_neon(x, y);
+            (sums.0 + similarity, sums.1 + norma, sums.2 + normb)
+        });
+        return Some(cosine(similarity, norma, normb));
+    }
+    None
+}
+
+#[cfg(feature = "halfvec")]
+pub(crate) fn half_l1_distance(a: &[f16], b: &[f16]) -> Option<f32> {
+    if has_neon() {
+        return Some(half_chunks(a, b, 0.0, |distance, x, y| unsafe {
+            distance + l1_distance_neon(x, y)
+        }));
+    }
+    None
+}
+
+pub(crate) fn sparse_inner_product(
+    a_indices: &[i32],
+    a_values: &[f32],
+    b_indices: &[i32],
+    b_values: &[f32],
+) -> Option<f32> {
+    // products are added in index order either way, so iterate over the
+    // vector with fewer elements and skip ahead in the other
+    let (a_indices, a_values, b_indices, b_values) = if a_indices.len() <= b_indices.len() {
+        (a_indices, a_values, b_indices, b_values)
+    } else {
+        (b_indices, b_values, a_indices, a_values)
+    };
+
+    if has_neon() {
+        return Some(unsafe {
+            sparse_inner_product_neon(a_indices, a_values, b_indices, b_values)
+        });
+    }
+    None
+}
+
+pub(crate) fn hamming_distance(a: &[u8], b: &[u8]) -> Option<u64> {
+    if has_neon() {
+        return Some(unsafe { hamming_distance_neon(a, b) });
+    }
+    None
+}
+
+pub(crate) fn jaccard_counts(a: &[u8], b: &[u8]) -> Option<(u64, u64, u64)> {
+    if has_neon() {
+        return Some(unsafe { jaccard_counts_neon(a, b) });
+    }
+    None
+}
+
+#[target_feature(enable = "neon")]
+unsafe fn l2_squared_distance_neon(a: &[f32], b: &[f32]) -> f32 {
+    let n = a.len().min(b.len());
+    let mut sum = vdupq_n_f32(0.0);
+    let mut i = 0;
+    while i + 4 <= n {
+        let diff = vsubq_f32(vld1q_f32(a.as_ptr().add(i)), vld1q_f32(b.as_ptr().add(i)));
+        sum = vfmaq_f32(sum, diff, diff);
+        i += 4;
+    }
+    let mut distance = vaddvq_f32(sum);
+    for j in i..n {
+        let diff = a[j] - b[j];
+        distance += diff * diff;
+    }
+    distance
+}
+
+#[target_feature(enable = "neon")]
+unsafe fn inner_product_neon(a: &[f32], b: &[f32]) -> f32 {
+    let n = a.len().min(b.len());
+    let mut sum = vdupq_n_f32(0.0);
+    let mut i = 0;
+    while i + 4 <= n {
+        sum = vfmaq_f32(
+            sum,
+            vld1q_f32(a.as_ptr().add(i)),
+            vld1q_f32(b.as_ptr().add(i)),
+        );
+        i += 4;
+    }
+    let mut distance = vaddvq_f32(sum);
+    for j in i..n {
+        distance += a[j] * b[j];
+    }
+    distance
+}
+
+#[target_feature(enable = "neon")]
+unsafe fn cosine_sums_neon(a: &[f32], b: &[f32]) -> (f32, f32, f32) {
+    let n = a.len().min(b.len());
+    let mut similarity = vdupq_n_f32(0.0);
+    let mut norma = vdupq_n_f32(0.0);
+    let mut normb = vdupq_n_f32(0.0);
+    let mut i = 0;
+    while i + 4 <= n {
+        let x = vld1q_f32(a.as_ptr().add(i));
+        let y = vld1q_f32(b.as_ptr().add(i));
+        similarity = vfmaq_f32(similarity, x, y);
+        norma = vfmaq_f32(norma, x, x);
+        normb = vfmaq_f32(normb, y, y);
+        i += 4;
+    }
+    let mut similarity = vaddvq_f32(similarity);
+    let mut norma = vaddvq_f32(norma);
+    let mut normb = vaddvq_f32(normb);
+    for j in i..n {
+        similarity += a[j] * b[j];
+        norma += a[j] * a[j];
+        normb += b[j] * b[j];
+    }
+    (similarity, norma, normb)
+}
+
+#[target_feature(enable = "neon")]
+unsafe fn cosine_similarity_neon(a: &[f32], b: &[f32]) -> f64 {
+    let (similarity, norma, normb) = cosine_sums_neon(a, b);
+    cosine(similarity, norma, normb)
+}
+
+#[target_feature(enable = "neon")]
+unsafe fn l1_distance_neon(a: &[f32], b: &[f32]) -> f32 {
+    let n = a.len().min(b.len());
+    let mut sum = vdupq_n_f32(0.0);
+    let mut i = 0;
+    while i + 4 <= n {
+        let diff = vsubq_f32(vld1q_f32(a.as_ptr().add(i)), vld1q_f32(b.as_ptr().add(i)));
+        sum = vaddq_f32(sum, vabsq_f32(diff));
+        i += 4;
+    }
+    let mut distance = vaddvq_f32(sum);
+    for j in i..n {
+        distance += (a[j] - b[j]).abs();
+    }
+    distance
+}
+
+// converts halfs to floats in chunks that stay in the L1 cache
+#[cfg(feature = "halfvec")]
+fn half_chunks<T, F: FnMut(T, &[f32], &[f32]) -> T>(a: &[f16], b: &[f16], init: T, mut f: F) -> T {
+    let mut acc = init;
+    let mut x = [0.0; 256];
+    let mut y = [0.0; 256];
+    for (a, b) in a.chunks(256).zip(b.chunks(256)) {
+        let n = a.len().min(b.len());
+        a[..n].convert_to_f32_slice(&mut x[..n]);
+        b[..n].convert_to_f32_slice(&mut y[..n]);
+        acc = f(acc, &x[..n], &y[..n]);
+    }
+    acc
+}
+
+#[target_feature(enable = "neon")]
+unsafe fn sparse_inner_product_neon(
+    a_indices: &[i32],
+    a_values: &[f32],
+    b_indices: &[i32],
+    b_values: &[f32],
+) -> f32 {
+    let mut distance = 0.0;
+    let mut bpos = 0;
+    for (ai, ax) in a_indices.iter().zip(a_values) {
+        // skip blocks of indices that are less than ai
+        let key = vdupq_n_s32(*ai);
+        while bpos + 4 <= b_indices.len() {
+            let less = vcltq_s32(vld1q_s32(b_indices.as_ptr().add(bpos)), key);
+            // each matching lane is all ones
+            let count = vaddvq_u32(less).wrapping_neg() as usize;
+            bpos += count;
+            if count < 4 {
+                break;
+            }
+        }
+        while bpos < b_indices.len() && b_indices[bpos] < *ai {
+            bpos += 1;
+        }
+        if bpos < b_indices.len() && b_indices[bpos] == *ai {
+            distance += ax * b_values[bpos];
+            bpos += 1;
+        }
+    }
+    distance
+}
+
+#[target_feature(enable = "neon")]
+unsafe fn hamming_distance_neon(a: &[u8], b: &[u8]) -> u64 {
+    let n = a.len().min(b.len());
+    let mut distance = 0;
+    let mut i = 0;
+    while i + 16 <= n {
+        let x = vld1q_u8(a.as_ptr().add(i));
+        let y = vld1q_u8(b.as_ptr().add(i));
+        distance += u64::from(vaddlvq_u8(vcntq_u8(veorq_u8(x, y))));
+        i += 16;
+    }
+    for j in i..n {
+        distance += u64::from((a[j] ^ b[j]).count_ones());
+    }
+    distance
+}
+
+#[target_feature(enable = "neon")]
+unsafe fn jaccard_counts_neon(a: &[u8], b: &[u8]) -> (u64, u64, u64) {
+    let n = a.len().min(b.len());
+    let mut ab = 0;
+    let mut aa = 0;
+    let mut bb = 0;
+    let mut i = 0;
+    while i + 16 <= n {
+        let x = vld1q_u8(a.as_ptr().add(i));
+        let y = vld1q_u8(b.as_ptr().add(i));
+        ab += u64::from(vaddlvq_u8(vcntq_u8(vandq_u8(x, y))));
+        aa += u64::from(vaddlvq_u8(vcntq_u8(x)));
+        bb += u64::from(vaddlvq_u8(vcntq_u8(y)));
+        i += 16;
+    }
+    for j in i..n {
+        ab += u64::from((a[j] & b[j]).count_ones());
+        aa += u64::from(a[j].count_ones());
+        bb += u64::from(b[j].count_ones());
+    }
+    (ab, aa, bb)
+}
+
+fn cosine(similarity: f32, norma: f32, normb: f32) -> f64 {
+    // use sqrt(a * b) over sqrt(a) * sqrt(b)
+    f64::from(similarity) / (f64::from(norma) * f64::from(normb)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distance::fixtures::{assert_close, values};
+    use crate::distance::scalar;
+
+    #[test]
+    fn test_f32() {
+        for n in [0, 1, 3, 4, 5, 100, 1536] {
+            let a = values(n, 1);
+            let b = values(n, 2);
+            assert_close(
+                scalar::l2_squared_distance(&a, &b).into(),
+                l2_squared_distance(&a, &b).unwrap().into(),
+            );
+            assert_close(
+                scalar::inner_product(&a, &b).into(),
+                inner_product(&a, &b).unwrap().into(),
+            );
+            assert_close(
+                scalar::l1_distance(&a, &b).into(),
+                l1_distance(&a, &b).unwrap().into(),
+            );
+            if n > 0 {
+                assert_close(
+                    scalar::cosine_similarity(&a, &b),
+                    cosine_similarity(&a, &b).unwrap(),
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "halfvec")]
+    #[test]
+    fn test_half() {
+        for n in [0, 1, 3, 255, 256, 257, 1536] {
+            let a: Vec<f16> = values(n, 3).into_iter().map(f16::from_f32).collect();
+            let b: Vec<f16> = values(n, 4).into_iter().map(f16::from_f32).collect();
+            assert_close(
+                scalar::half_l2_squared_distance(&a, &b).into(),
+                half_l2_squared_distance(&a, &b).unwrap().into(),
+            );
+            assert_close(
+                scalar::half_inner_product(&a, &b).into(),
+                half_inner_product(&a, &b).unwrap().into(),
+            );
+            assert_close(
+                scalar::half_l1_distance(&a, &b).into(),
+                half_l1_distance(&a, &b).unwrap().into(),
+            );
+            if n > 0 {
+                assert_close(
+                    scalar::half_cosine_similarity(&a, &b),
+                    half_cosine_similarity(&a, &b).unwrap(),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_sparse_inner_product() {
+        let a_indices: Vec<i32> = (0..500).map(|i| i * 3).collect();
+        let b_indices: Vec<i32> = (0..300).map(|i| i * 5 + 1).collect();
+        let a_values = values(a_indices.len(), 5);
+        let b_values = values(b_indices.len(), 6);
+        assert_eq!(
+            scalar::sparse_inner_product(&a_indices, &a_values, &b_indices, &b_values),
+            sparse_inner_product(&a_indices, &a_values, &b_indices, &b_values).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_bit() {
+        for n in [0, 1, 15, 16, 17, 200] {
+            let a: Vec<u8> = (0..n).map(|i| (i * 37 + 11) as u8).collect();
+            let b: Vec<u8> = (0..n).map(|i| (i * 91 + 5) as u8).collect();
+            assert_eq!(
+                scalar::hamming_distance(&a, &b),
+                hamming_distance(&a, &b).unwrap()
+            );
+            assert_eq!(
+                scalar::jaccard_counts(&a, &b),
+                jaccard_counts(&a, &b).unwrap()
+            );
+        }
+    }
+}
@@ -0,0 +1,136 @@
+// Distance kernels, dispatching to SIMD implementations when the simd
+// feature is enabled and the CPU supports them
+
+mod scalar;
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod x86;
+
+#[cfg(all(feature = "simd", target_arch = "aarch64"))]
+mod aarch64;
+
+#[cfg(feature = "halfvec")]
+use half::f16;
+
+// shared by the tests of the SIMD kernels
+#[cfg(all(
+    test,
+    feature = "simd",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+mod fixtures {
+    // deterministic values in [-1, 1)
+    pub(super) fn values(n: usize, seed: u32) -> Vec<f32> {
+        let mut state = seed;
+        (0..n)
+            .map(|_| {
+                state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+                (state >> 8) as f32 / (1 << 23) as f32 - 1.0
+            })
+            .collect()
+    }
+
+    pub(super) fn assert_close(expected: f64, actual: f64) {
+        let tolerance = 1e-4 * expected.abs().max(1.0);
+        assert!(
+            (expected - actual).abs() <= tolerance,
+            "expected {} but got {}",
+            expected,
+            actual
+        );
+    }
+}
+
+// returns early with the SIMD result when one is available
+macro_rules! simd {
+    ($f:ident($($arg:expr),*)) => {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        if let Some(v) = x86::$f($($arg),*) {
+            return v;
+        }
+
+        #[cfg(all(feature = "simd", target_arch = "aarch64"))]
+        if let Some(v) = aarch64::$f($($arg),*) {
+            return v;
+        }
+    };
+}
+
+pub(crate) fn l2_squared_distance(a: &[f32], b: &[f32]) -> f32 {
+    simd!(l2_squared_distance(a, b));
+    scalar::l2_squared_distance(a, b)
+}
+
+pub(crate) fn inner_product(a: &[f32], b: &[f32]) -> f32 {
+    simd!(inner_product(a, b));
+    scalar::inner_product(a, b)
+}
+
+pub(crate) fn cosine_similarity(a: &[f32], b: &[f32]) -> f64 {
+    simd!(cosine_similarity(a, b));
+    scalar::cosine_similarity(a, b)
+}
+
+pub(crate) fn l1_distance(a: &[f32], b: &[f32]) -> f32 {
+    simd!(l1_distance(a, b));
+    scalar::l1_distance(a, b)
+}
+
+#[cfg(feature = "halfvec")]
+pub(crate) fn half_l2_squared_distance(a: &[f16], b: &[f16]) -> f32 {
+    simd!(half_l2_squared_distance(a, b));
+    scalar::half_l2_squared_distance(a, b)
+}
+
+#[cfg(feature = "halfvec")]
+pub(crate) fn half_inner_product(a: &[f16], b: &[f16]) -> f32 {
+    simd!(half_inner_product(a, b));
+    scalar::half_inner_product(a, b)
+}
+
+#[cfg(feature = "halfvec")]
+pub(crate) fn half_cosine_similarity(a: &[f16], b: &[f16]) -> f64 {
+    simd!(half_cosine_similarity(a, b));
+    scalar::half_cosine_similarity(a, b)
+}
+
+#[cfg(feature = "halfvec")]
+pub(crate) fn half_l1_distance(a: &[f16], b: &[f16]) -> f32 {
+    simd!(half_l1_distance(a, b));
+    scalar::half_l1_distance(a, b)
+}
+
+// every non-zero element contributes to these, so there is nothing to skip
+pub(crate) use scalar::{sparse_l1_distance, sparse_l2_squared_distance};
+
+pub(crate) fn sparse_inner_product(
+    a_indices: &[i32],
+    a_values: &[f32],
+    b_indices: &[i32],
+    b_values: &[f32],
+) -> f32 {
+    simd!(sparse_inner_product(
+        a_indices, a_values, b_indices, b_values
+    ));
+    scalar::sparse_inner_product(a_indices, a_values, b_indices, b_values)
+}
+
+pub(crate) fn squared_norm(values: &[f32]) -> f32 {
+    inner_product(values, values)
+}
+
+pub(crate) fn hamming_distance(a: &[u8], b: &[u8]) -> u64 {
+    simd!(hamming_distance(a, b));
+    scalar::hamming_distance(a, b)
+}
+
+pub(crate) fn jaccard_counts(a: &[u8], b: &[u8]) -> (u64, u64, u64) {
+    simd!(jaccard_counts(a, b));
+    scalar::jaccard_counts(a, b)
+}
+
+// converts a cosine similarity to a distance like pgvector
+pub(crate) fn cosine_distance(similarity: f64) -> f64 {
+    // keep in range
+    1.0 - similarity.clamp(-1.0, 1.0)
+}
@@ -0,0 +1,181 @@
+// Scalar kernels that follow the accumulation in pgvector, which sums in
+// f32 and only converts to f64 for the final result
+
+#[cfg(feature = "halfvec")]
+use half::f16;
+
+pub(crate) fn l2_squared_distance(a: &[f32], b: &[f32]) -> f32 {
+    let mut distance = 0.0;
+    for (x, y) in a.iter().zip(b) {
+        let diff = x - y;
+        distance += diff * diff;
+    }
+    distance
+}
+
+pub(crate) fn inner_product(a: &[f32], b: &[f32]) -> f32 {
+    let mut distance = 0.0;
+    for (x, y) in a.iter().zip(b) {
+        distance += x * y;
+    }
+    distance
+}
+
+pub(crate) fn cosine_similarity(a: &[f32], b: &[f32]) -> f64 {
+    let mut similarity: f32 = 0.0;
+    let mut norma: f32 = 0.0;
+    let mut normb: f32 = 0.0;
+    for (x, y) in a.iter().zip(b) {
+        similarity += x * y;
+        norma += x * x;
+        normb += y * y;
+    }
+
+    // use sqrt(a * b) over sqrt(a) * sqrt(b)
+    f64::from(similarity) / (f64::from(norma) * f64::from(normb)).sqrt()
+}
+
+pub(crate) fn l1_distance(a: &[f32], b: &[f32]) -> f32 {
+    let mut distance = 0.0;
+    for (x, y) in a.iter().zip(b) {
+        distance += (x - y).abs();
+    }
+    distance
+}
+
+#[cfg(feature = "halfvec")]
+pub(crate) fn half_l2_squared_distance(a: &[f16], b: &[f16]) -> f32 {
+    let mut distance = 0.0;
+    for (x, y) in a.iter().zip(b) {
+        let diff = x.to_f32() - y.to_f32();
+        distance += diff * diff;
+    }
+    distance
+}
+
+#[cfg(feature = "halfvec")]
+pub(crate) fn half_inner_product(a: &[f16], b: &[f16]) -> f32 {
+    let mut distance = 0.0;
+    for (x, y) in a.iter().zip(b) {
+        distance += x.to_f32() * y.to_f32();
+    }
+    distance
+}
+
+#[cfg(feature = "halfvec")]
+pub(crate) fn half_cosine_similarity(a: &[f16], b: &[f16]) -> f64 {
+    let mut similarity: f32 = 0.0;
+    let mut norma: f32 = 0.0;
+    let mut normb: f32 = 0.0;
+    for (x, y) in a.iter().zip(b) {
+        let (x, y) = (x.to_f32(), y.to_f32());
+        similarity += x * y;
+        norma += x * x;
+        normb += y * y;
+    }
+
+    // use sqrt(a * b) over sqrt(a) * sqrt(b)
+    f64::from(similarity) / (f64::from(norma) * f64::from(normb)).sqrt()
+}
+
+#[cfg(feature = "halfvec")]
+pub(crate) fn half_l1_distance(a: &[f16], b: &[f16]) -> f32 {
+    let mut distance = 0.0;
+    for (x, y) in a.iter().zip(b) {
+        distance += (x.to_f32() - y.to_f32()).abs();
+    }
+    distance
+}
+
+// merges two sorted index lists, calling f with the values at each index
+// that is present in either vector (using zero for missing elements)
+fn sparse_merge<F: FnMut(f32, f32)>(
+    a_indices: &[i32],
+    a_values: &[f32],
+    b_indices: &[i32],
+    b_values: &[f32],
+    mut f: F,
+) {
+    let mut bpos = 0;
+    for (ai, ax) in a_indices.iter().zip(a_values) {
+        while bpos < b_indices.len() && b_indices[bpos] < *ai {
+            f(0.0, b_values[bpos]);
+            bpos += 1;
+        }
+        if bpos < b_indices.len() && b_indices[bpos] == *ai {
+            f(*ax, b_values[bpos]);
+            bpos += 1;
+        } else {
+            f(*ax, 0.0);
+        }
+    }
+    for bx in &b_values[bpos..] {
+        f(0.0, *bx);
+    }
+}
+
+pub(crate) fn sparse_l2_squared_distance(
+    a_indices: &[i32],
+    a_values: &[f32],
+    b_indices: &[i32],
+    b_values: &[f32],
+) -> f32 {
+    let mut distance = 0.0;
+    sparse_merge(a_indices, a_values, b_indices, b_values, |x, y| {
+        let diff = x - y;
+        distance += diff * diff;
+    });
+    distance
+}
+
+pub(crate) fn sparse_inner_product(
+    a_indices: &[i32],
+    a_values: &[f32],
+    b_indices: &[i32],
+    b_values: &[f32],
+) -> f32 {
+    let mut distance = 0.0;
+    let mut bpos = 0;
+    for (ai, ax) in a_indices.iter().zip(a_values) {
+        while bpos < b_indices.len() && b_indices[bpos] < *ai {
+            bpos += 1;
+        }
+        if bpos < b_indices.len() && b_indices[bpos] == *ai {
+            distance += ax * b_values[bpos];
+            bpos += 1;
+        }
+    }
+    distance
+}
+
+pub(crate) fn sparse_l1_distance(
+    a_indices: &[i32],
+    a_values: &[f32],
+    b_indices: &[i32],
+    b_values: &[f32],
+) -> f32 {
+    let mut distance = 0.0;
+    sparse_merge(a_indices, a_values, b_indices, b_values, |x, y| {
+        distance += (x - y).abs();
+    });
+    distance
+}
+
+pub(crate) fn hamming_distance(a: &[u8], b: &[u8]) -> u64 {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| u64::from((x ^ y).count_ones()))
+        .sum()
+}
+
+pub(crate) fn jaccard_counts(a: &[u8], b: &[u8]) -> (u64, u64, u64) {
+    let mut ab = 0;
+    let mut aa = 0;
+    let mut bb = 0;
+    for (x, y) in a.iter().zip(b) {
+        ab += u64::from((x & y).count_ones());
+        aa += u64::from(x.count_ones());
+        bb += u64::from(y.count_ones());
+    }
+    (ab, aa, bb)
+}
@@ -0,0 +1,773 @@
+// AVX-512 and AVX2 kernels with runtime detection
+//
+// The AVX-512 intrinsics require Rust 1.89+ (and the F16C intrinsics 1.68+),
+// which is why the simd feature has a higher minimum Rust version than the
+// rest of the crate. The MSRV lint is only allowed on functions that use them.
+
+use std::arch::x86_64::*;
+
+#[cfg(feature = "halfvec")]
+use half::f16;
+
+fn has_avx512() -> bool {
+    is_x86_feature_detected!("avx512f")
+}
+
+fn has_avx2() -> bool {
+    is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma")
+}
+
+#[cfg(feature = "halfvec")]
+fn has_f16c() -> bool {
+    has_avx2() && is_x86_feature_detected!("f16c")
+}
+
+pub(crate) fn l2_squared_distance(a: &[f32], b: &[f32]) -> Option<f32> {
+    if has_avx512() {
+        return Some(unsafe { l2_squared_distance_avx512(a, b) });
+    }
+    if has_avx2() {
+        return Some(unsafe { l2_squared_distance_avx2(a, b) });
+    }
+    None
+}
+
+pub(crate) fn inner_product(a: &[f32], b: &[f32]) -> Option<f32> {
+    if has_avx512() {
+        return Some(unsafe { inner_product_avx512(a, b) });
+    }
+    if has_avx2() {
+        return Some(unsafe { inner_product_avx2(a, b) });
+    }
+    None
+}
+
+pub(crate) fn cosine_similarity(a: &[f32], b: &[f32]) -> Option<f64> {
+    if has_avx512() {
+        return Some(unsafe { cosine_similarity_avx512(a, b) });
+    }
+    if has_avx2() {
+        return Some(unsafe { cosine_similarity_avx2(a, b) });
+    }
+    None
+}
+
+pub(crate) fn l1_distance(a: &[f32], b: &[f32]) -> Option<f32> {
+    if has_avx512() {
+        return Some(unsafe { l1_distance_avx512(a, b) });
+    }
+    if has_avx2() {
+        return Some(unsafe { l1_distance_avx2(a, b) });
+    }
+    None
+}
+
+#[cfg(feature = "halfvec")]
+pub(crate) fn half_l2_squared_distance(a: &[f16], b: &[f16]) -> Option<f32> {
+    if has_avx512() {
+        return Some(unsafe { half_l2_squared_distance_avx512(a, b) });
+    }
+    if has_f16c() {
+        return Some(unsafe { half_l2_squared_distance_f16c(a, b) });
+    }
+    None
+}
+
+#[cfg(feature = "halfvec")]
+pub(crate) fn half_inner_product(a: &[f16], b: &[f16]) -> Option<f32> {
+    if has_avx512() {
+        return Some(unsafe { half_inner_product_avx512(a, b) });
+    }
+    if has_f16c() {
+        return Some(unsafe { half_inner_product_f16c(a, b) });
+    }
+    None
+}
+
+#[cfg(feature = "halfvec")]
+pub(crate) fn half_cosine_similarity(a: &[f16], b: &[f16]) -> Option<f64> {
+    if has_avx512() {
+        return Some(unsafe { half_cosine_similarity_avx512(a, b) });
+    }
+    if has_f16c() {
+        return Some(unsafe { half_cosine_similarity_f16c(a, b) });
+    }
+    None
+}
+
+#[cfg(feature = "halfvec")]
+pub(crate) fn half_l1_distance(a: &[f16], b: &[f16]) -> Option<f32> {
+    if has_avx512() {
+        return Some(unsafe { half_l1_distance_avx512(a, b) });
+    }
+    if has_f16c() {
+        return Some(unsafe { half_l1_distance_f16c(a, b) });
+    }
+    None
+}
+
+pub(crate) fn sparse_inner_product(
+    a_indices: &[i32],
+    a_values: &[f32],
+    b_indices: &[i32],
+    b_values: &[f32],
+) -> Option<f32> {
+    // products are added in index order either way, so iterate over the
+    // vector with fewer elements and skip ahead in the other
+    let (a_indices, a_values, b_indices, b_values) = if a_indices.len() <= b_indices.len() {
+        (a_indices, a_values, b_indices, b_values)
+    } else {
+        (b_indices, b_values, a_indices, a_values)
+    };
+
+    if has_avx512() {
+        return Some(unsafe {
+            sparse_inner_product_avx512(a_indices, a_values, b_indices, b_values)
+        });
+    }
+    if has_avx2() {
+        return Some(unsafe {
+            sparse_inner_product_avx2(a_indices, a_values, b_indices, b_values)
+        });
+    }
+    None
+}
+
+pub(crate) fn hamming_distance(a: &[u8], b: &[u8]) -> Option<u64> {
+    if is_x86_feature_detected!("avx512vpopcntdq") {
+        return Some(unsafe { hamming_distance_avx512(a, b) });
+    }
+    if is_x86_feature_detected!("popcnt") {
+        return Some(unsafe { hamming_distance_popcnt(a, b) });
+    }
+    None
+}
+
+pub(crate) fn jaccard_counts(a: &[u8], b: &[u8]) -> Option<(u64, u64, u64)> {
+    if is_x86_feature_detected!("avx512vpopcntdq") {
+        return Some(unsafe { jaccard_counts_avx512(a, b) });
+    }
+    if is_x86_feature_detected!("popcnt") {
+        return Some(unsafe { jaccard_counts_popcnt(a, b) });
+    }
+    None
+}
+
+#[target_feature(enable = "avx")]
+unsafe fn hsum256(v: __m256) -> f32 {
+    let s = _mm_add_ps(_mm256_castps256_ps128(v), _mm256_extractf128_ps(v, 1));
+    let s = _mm_add_ps(s, _mm_movehl_ps(s, s));
+    let s = _mm_add_ss(s, _mm_shuffle_ps(s, s, 1));
+    _mm_cvtss_f32(s)
+}
+
+#[allow(clippy::incompatible_msrv)]
+fn mask16(remaining: usize) -> __mmask16 {
+    ((1_u32 << remaining) - 1) as __mmask16
+}
+
+#[allow(clippy::incompatible_msrv)]
+#[target_feature(enable = "avx512f")]
+unsafe fn l2_squared_distance_avx512(a: &[f32], b: &[f32]) -> f32 {
+    let n = a.len().min(b.len());
+    let mut sum = _mm512_setzero_ps();
+    let mut i = 0;
+    while i < n {
+        let mask = mask16((n - i).min(16));
+        let x = _mm512_maskz_loadu_ps(mask, a.as_ptr().add(i));
+        let y = _mm512_maskz_loadu_ps(mask, b.as_ptr().add(i));
+        let diff = _mm512_sub_ps(x, y);
+        sum = _mm512_fmadd_ps(diff, diff, sum);
+        i += 16;
+    }
+    _mm512_reduce_add_ps(sum)
+}
+
+#[target_feature(enable = "avx2,fma")]
+unsafe fn l2_squared_distance_avx2(a: &[f32], b: &[f32]) -> f32 {
+    let n = a.len().min(b.len());
+    let mut sum = _mm256_setzero_ps();
+    let mut i = 0;
+    while i + 8 <= n {
+        let x = _mm256_loadu_ps(a.as_ptr().add(i));
+        let y = _mm256_loadu_ps(b.as_ptr().add(i));
+        let diff = _mm256_sub_ps(x, y);
+        sum = _mm256_fmadd_ps(diff, diff, sum);
+        i += 8;
+    }
+    let mut distance = hsum256(sum);
+    for j in i..n {
+        let diff = a[j] - b[j];
+        distance += diff * diff;
+    }
+    distance
+}
+
+#[allow(clippy::incompatible_msrv)]
+#[target_feature(enable = "avx512f")]
+unsafe fn inner_product_avx512(a: &[f32], b: &[f32]) -> f32 {
+    let n = a.len().min(b.len());
+    let mut sum = _mm512_setzero_ps();
+    let mut i = 0;
+    while i < n {
+        let mask = mask16((n - i).min(16));
+        let x = _mm512_maskz_loadu_ps(mask, a.as_ptr().add(i));
+        let y = _mm512_maskz_loadu_ps(mask, b.as_ptr().add(i));
+        sum = _mm512_fmadd_ps(x, y, sum);
+        i += 16;
+    }
+    _mm512_reduce_add_ps(sum)
+}
+
+#[target_feature(enable = "avx2,fma")]
+unsafe fn inner_product_avx2(a: &[f32], b: &[f32]) -> f32 {
+    let n = a.len().min(b.len());
+    let mut sum = _mm256_setzero_ps();
+    let mut i = 0;
+    while i + 8 <= n {
+        let x = _mm256_loadu_ps(a.as_ptr().add(i));
+        let y = _mm256_loadu_ps(b.as_ptr().add(i));
+        sum = _mm256_fmadd_ps(x, y, sum);
+        i += 8;
+    }
+    let mut distance = hsum256(sum);
+    for j in i..n {
+        distance += a[j] * b[j];
+    }
+    distance
+}
+
+#[allow(clippy::incompatible_msrv)]
+#[target_feature(enable = "avx512f")]
+unsafe fn cosine_similarity_avx512(a: &[f32], b: &[f32]) -> f64 {
+    let n = a.len().min(b.len());
+    let mut similarity = _mm512_setzero_ps();
+    let mut norma = _mm512_setzero_ps();
+    let mut normb = _mm512_setzero_ps();
+    let mut i = 0;
+    while i < n {
+        let mask = mask16((n - i).min(16));
+        let x = _mm512_maskz_loadu_ps(mask, a.as_ptr().add(i));
+        let y = _mm512_maskz_loadu_ps(mask, b.as_ptr().add(i));
+        similarity = _mm512_fmadd_ps(x, y, similarity);
+        norma = _mm512_fmadd_ps(x, x, norma);
+        normb = _mm512_fmadd_ps(y, y, normb);
+        i += 16;
+    }
+    cosine(
+        _mm512_reduce_add_ps(similarity),
+        _mm512_reduce_add_ps(norma),
+        _mm512_reduce_add_ps(normb),
+    )
+}
+
+#[target_feature(enable = "avx2,fma")]
+unsafe fn cosine_similarity_avx2(a: &[f32], b: &[f32]) -> f64 {
+    let n = a.len().min(b.len());
+    let mut similarity = _mm256_setzero_ps();
+    let mut norma = _mm256_setzero_ps();
+    let mut normb = _mm256_setzero_ps();
+    let mut i = 0;
+    while i + 8 <= n {
+        let x = _mm256_loadu_ps(a.as_ptr().add(i));
+        let y = _mm256_loadu_ps(b.as_ptr().add(i));
+        similarity = _mm256_fmadd_ps(x, y, similarity);
+        norma = _mm256_fmadd_ps(x, x, norma);
+        normb = _mm256_fmadd_ps(y, y, normb);
+        i += 8;
+    }
+    let mut similarity = hsum256(similarity);
+    let mut norma = hsum256(norma);
+    let mut normb = hsum256(normb);
+    for j in i..n {
+        similarity += a[j] * b[j];
+        norma += a[j] * a[j];
+        normb += b[j] * b[j];
+    }
+    cosine(similarity, norma, normb)
+}
+
+#[allow(clippy::incompatible_msrv)]
+#[target_feature(enable = "avx512f")]
+unsafe fn l1_distance_avx512(a: &[f32], b: &[f32]) -> f32 {
+    let n = a.len().min(b.len());
+    let mut sum = _mm512_setzero_ps();
+    let mut i = 0;
+    while i < n {
+        let mask = mask16((n - i).min(16));
+        let x = _mm512_maskz_loadu_ps(mask, a.as_ptr().add(i));
+        let y = _mm512_maskz_loadu_ps(mask, b.as_ptr().add(i));
+        sum = _mm512_add_ps(sum, _mm512_abs_ps(_mm512_sub_ps(x, y)));
+        i += 16;
+    }
+    _mm512_reduce_add_ps(sum)
+}
+
+#[target_feature(enable = "avx2,fma")]
+unsafe fn l1_distance_avx2(a: &[f32], b: &[f32]) -> f32 {
+    let n = a.len().min(b.len());
+    let sign = _mm256_set1_ps(-0.0);
+    let mut sum = _mm256_setzero_ps();
+    let mut i = 0;
+    while i + 8 <= n {
+        let x = _mm256_loadu_ps(a.as_ptr().add(i));
+        let y = _mm256_loadu_ps(b.as_ptr().add(i));
+        sum = _mm256_add_ps(sum, _mm256_andnot_ps(sign, _mm256_sub_ps(x, y)));
+        i += 8;
+    }
+    let mut distance = hsum256(sum);
+    for j in i..n {
+        distance += (a[j] - b[j]).abs();
+    }
+    distance
+}
+
+// loads 16 halfs (or fewer at the end) and converts them to floats
+#[allow(clippy::incompatible_msrv)]
+#[cfg(feature = "halfvec")]
+#[target_feature(enable = "avx512f")]
+unsafe fn load_half_avx512(v: &[f16], i: usize) -> __m512 {
+    if v.len() - i >= 16 {
+        _mm512_cvtph_ps(_mm256_loadu_si256(v.as_ptr().add(i) as *const __m256i))
+    } else {
+        let mut buf = [f16::ZERO; 16];
+        buf[..v.len() - i].copy_from_slice(&v[i..]);
+        _mm512_cvtph_ps(_mm256_loadu_si256(buf.as_ptr() as *const __m256i))
+    }
+}
+
+#[allow(clippy::incompatible_msrv)]
+#[cfg(feature = "halfvec")]
+#[target_feature(enable = "avx,f16c")]
+unsafe fn load_half_f16c(v: &[f16], i: usize) -> __m256 {
+    _mm256_cvtph_ps(_mm_loadu_si128(v.as_ptr().add(i) as *const __m128i))
+}
+
+#[allow(clippy::incompatible_msrv)]
+#[cfg(feature = "halfvec")]
+#[target_feature(enable = "avx512f")]
+unsafe fn half_l2_squared_distance_avx512(a: &[f16], b: &[f16]) -> f32 {
+    let (a, b) = same_len(a, b);
+    let mut sum = _mm512_setzero_ps();
+    for i in (0..a.len()).step_by(16) {
+        let diff = _mm512_sub_ps(load_half_avx512(a, i), load_half_avx512(b, i));
+        sum = _mm512_fmadd_ps(diff, diff, sum);
+    }
+    _mm512_reduce_add_ps(sum)
+}
+
+#[cfg(feature = "halfvec")]
+#[target_feature(enable = "avx2,fma,f16c")]
+unsafe fn half_l2_squared_distance_f16c(a: &[f16], b: &[f16]) -> f32 {
+    let n = a.len().min(b.len());
+    let mut sum = _mm256_setzero_ps();
+    let mut i = 0;
+    while i + 8 <= n {
+        let diff = _mm256_sub_ps(load_half_f16c(a, i), load_half_f16c(b, i));
+        sum = _mm256_fmadd_ps(diff, diff, sum);
+        i += 8;
+    }
+    let mut distance = hsum256(sum);
+    for j in i..n {
+        let diff = a[j].to_f32() - b[j].to_f32();
+        distance += diff * diff;
+    }
+    distance
+}
+
+#[allow(clippy::incompatible_msrv)]
+#[cfg(feature = "halfvec")]
+#[target_feature(enable = "avx512f")]
+unsafe fn half_inner_product_avx512(a: &[f16], b: &[f16]) -> f32 {
+    let (a, b) = same_len(a, b);
+    let mut sum = _mm512_setzero_ps();
+    for i in (0..a.len()).step_by(16) {
+        sum = _mm512_fmadd_ps(load_half_avx512(a, i), load_half_avx512(b, i), sum);
+    }
+    _mm512_reduce_add_ps(sum)
+}
+
+#[cfg(feature = "halfvec")]
+#[target_feature(enable = "avx2,fma,f16c")]
+unsafe fn half_inner_product_f16c(a: &[f16], b: &[f16]) -> f32 {
+    let n = a.len().min(b.len());
+    let mut sum = _mm256_setzero_ps();
+    let mut i = 0;
+    while i + 8 <= n {
+        sum = _mm256_fmadd_ps(load_half_f16c(a, i), load_half_f16c(b, i), sum);
+        i += 8;
+    }
+    let mut distance = hsum256(sum);
+    for j in i..n {
+        distance += a[j].to_f32() * b[j].to_f32();
+    }
+    distance
+}
+
+#[allow(clippy::incompatible_msrv)]
+#[cfg(feature = "halfvec")]
+#[target_feature(enable = "avx512f")]
+unsafe fn half_cosine_similarity_avx512(a: &[f16], b: &[f16]) -> f64 {
+    let (a, b) = same_len(a, b);
+    let mut similarity = _mm512_setzero_ps();
+    let mut norma = _mm512_setzero_ps();
+    let mut normb = _mm512_setzero_ps();
+    for i in (0..a.len()).step_by(16) {
+        let x = load_half_avx512(a, i);
+        let y = load_half_avx512(b, i);
+        similarity = _mm512_fmadd_ps(x, y, similarity);
+        norma = _mm512_fmadd_ps(x, x, norma);
+        normb = _mm512_fmadd_ps(y, y, normb);
+    }
+    cosine(
+        _mm512_reduce_add_ps(similarity),
+        _mm512_reduce_add_ps(norma),
+        _mm512_reduce_add_ps(normb),
+    )
+}
+
+#[cfg(feature = "halfvec")]
+#[target_feature(enable = "avx2,fma,f16c")]
+unsafe fn half_cosine_similarity_f16c(a: &[f16], b: &[f16]) -> f64 {
+    let n = a.len().min(b.len());
+    let mut similarity = _mm256_setzero_ps();
+    let mut norma = _mm256_setzero_ps();
+    let mut normb = _mm256_setzero_ps();
+    let mut i = 0;
+    while i + 8 <= n {
+        let x = load_half_f16c(a, i);
+        let y = load_half_f16c(b, i);
+        similarity = _mm256_fmadd_ps(x, y, similarity);
+        norma = _mm256_fmadd_ps(x, x, norma);
+        normb = _mm256_fmadd_ps(y, y, normb);
+        i += 8;
+    }
+    let mut similarity = hsum256(similarity);
+    let mut norma = hsum256(norma);
+    let mut normb = hsum256(normb);
+    for j in i..n {
+        let (x, y) = (a[j].to_f32(), b[j].to_f32());
+        similarity += x * y;
+        norma += x * x;
+        normb += y * y;
+    }
+    cosine(similarity, norma, normb)
+}
+
+#[allow(clippy::incompatible_msrv)]
+#[cfg(feature = "halfvec")]
+#[target_feature(enable = "avx512f")]
+unsafe fn half_l1_distance_avx512(a: &[f16], b: &[f16]) -> f32 {
+    let (a, b) = same_len(a, b);
+    let mut sum = _mm512_setzero_ps();
+    for i in (0..a.len()).step_by(16) {
+        let diff = _mm512_sub_ps(load_half_avx512(a, i), load_half_avx512(b, i));
+        sum = _mm512_add_ps(sum, _mm512_abs_ps(diff));
+    }
+    _mm512_reduce_add_ps(sum)
+}
+
+#[cfg(feature = "halfvec")]
+#[target_feature(enable = "avx2,fma,f16c")]
+unsafe fn half_l1_distance_f16c(a: &[f16], b: &[f16]) -> f32 {
+    let n = a.len().min(b.len());
+    let sign = _mm256_set1_ps(-0.0);
+    let mut sum = _mm256_setzero_ps();
+    let mut i = 0;
+    while i + 8 <= n {
+        let diff = _mm256_sub_ps(load_half_f16c(a, i), load_half_f16c(b, i));
+        sum = _mm256_add_ps(sum, _mm256_andnot_ps(sign, diff));
+        i += 8;
+    }
+    let mut distance = hsum256(sum);
+    for j in i..n {
+        distance += (a[j].to_f32() - b[j].to_f32()).abs();
+    }
+    distance
+}
+
+#[allow(clippy::incompatible_msrv)]
+#[target_feature(enable = "avx512f")]
+unsafe fn sparse_inner_product_avx512(
+    a_indices: &[i32],
+    a_values: &[f32],
+    b_indices: &[i32],
+    b_values: &[f32],
+) -> f32 {
+    let mut distance = 0.0;
+    let mut bpos = 0;
+    for (ai, ax) in a_indices.iter().zip(a_values) {
+        // skip blocks of indices that are less than ai
+        let key = _mm512_set1_epi32(*ai);
+        while bpos + 16 <= b_indices.len() {
+            let block = _mm512_loadu_si512(b_indices.as_ptr().add(bpos) as *const _);
+            let count = _mm512_cmplt_epi32_mask(block, key).count_ones() as usize;
+            bpos += count;
+            if count < 16 {
+                break;
+            }
+        }
+        while bpos < b_indices.len() && b_indices[bpos] < *ai {
+            bpos += 1;
+        }
+        if bpos < b_indices.len() && b_indices[bpos] == *ai {
+            distance += ax * b_values[bpos];
+            bpos += 1;
+        }
+    }
+    distance
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn sparse_inner_product_avx2(
+    a_indices: &[i32],
+    a_values: &[f32],
+    b_indices: &[i32],
+    b_values: &[f32],
+) -> f32 {
+    let mut distance = 0.0;
+    let mut bpos = 0;
+    for (ai, ax) in a_indices.iter().zip(a_values) {
+        // skip blocks of indices that are less than ai
+        let key = _mm256_set1_epi32(*ai);
+        while bpos + 8 <= b_indices.len() {
+            let block = _mm256_loadu_si256(b_indices.as_ptr().add(bpos) as *const __m256i);
+            let less = _mm256_castsi256_ps(_mm256_cmpgt_epi32(key, block));
+            let count = _mm256_movemask_ps(less).count_ones() as usize;
+            bpos += count;
+            if count < 8 {
+                break;
+            }
+        }
+        while bpos < b_indices.len() && b_indices[bpos] < *ai {
+            bpos += 1;
+        }
+        if bpos < b_indices.len() && b_indices[bpos] == *ai {
+            distance += ax * b_values[bpos];
+            bpos += 1;
+        }
+    }
+    distance
+}
+
+#[allow(clippy::incompatible_msrv)]
+#[target_feature(enable = "avx512f,avx512vpopcntdq")]
+unsafe fn hamming_distance_avx512(a: &[u8], b: &[u8]) -> u64 {
+    let n = a.len().min(b.len());
+    let mut sum = _mm512_setzero_si512();
+    let mut i = 0;
+    while i + 64 <= n {
+        let x = _mm512_loadu_si512(a.as_ptr().add(i) as *const _);
+        let y = _mm512_loadu_si512(b.as_ptr().add(i) as *const _);
+        sum = _mm512_add_epi64(sum, _mm512_popcnt_epi64(_mm512_xor_si512(x, y)));
+        i += 64;
+    }
+    _mm512_reduce_add_epi64(sum) as u64 + hamming_distance_popcnt(&a[i..n], &b[i..n])
+}
+
+#[target_feature(enable = "popcnt")]
+unsafe fn hamming_distance_popcnt(a: &[u8], b: &[u8]) -> u64 {
+    let mut distance = 0;
+    let mut a_chunks = a.chunks_exact(8);
+    let mut b_chunks = b.chunks_exact(8);
+    for (x, y) in (&mut a_chunks).zip(&mut b_chunks) {
+        distance += u64::from((read_u64(x) ^ read_u64(y)).count_ones());
+    }
+    for (x, y) in a_chunks.remainder().iter().zip(b_chunks.remainder()) {
+        distance += u64::from((x ^ y).count_ones());
+    }
+    distance
+}
+
+#[allow(clippy::incompatible_msrv)]
+#[target_feature(enable = "avx512f,avx512vpopcntdq")]
+unsafe fn jaccard_counts_avx512(a: &[u8], b: &[u8]) -> (u64, u64, u64) {
+    let n = a.len().min(b.len());
+    let mut ab = _mm512_setzero_si512();
+    let mut aa = _mm512_setzero_si512();
+    let mut bb = _mm512_setzero_si512();
+    let mut i = 0;
+    while i + 64 <= n {
+        let x = _mm512_loadu_si512(a.as_ptr().add(i) as *const _);
+        let y = _mm512_loadu_si512(b.as_ptr().add(i) as *const _);
+        ab = _mm512_add_epi64(ab, _mm512_popcnt_epi64(_mm512_and_si512(x, y)));
+        aa = _mm512_add_epi64(aa, _mm512_popcnt_epi64(x));
+        bb = _mm512_add_epi64(bb, _mm512_popcnt_epi64(y));
+        i += 64;
+    }
+    let (tab, taa, tbb) = jaccard_counts_popcnt(&a[i..n], &b[i..n]);
+    (
+        _mm512_reduce_add_epi64(ab) as u64 + tab,
+        _mm512_reduce_add_epi64(aa) as u64 + taa,
+        _mm512_reduce_add_epi64(bb) as u64 + tbb,
+    )
+}
+
+#[target_feature(enable = "popcnt")]
+unsafe fn jaccard_counts_popcnt(a: &[u8], b: &[u8]) -> (u64, u64, u64) {
+    let mut ab = 0;
+    let mut aa = 0;
+    let mut bb = 0;
+    let mut a_chunks = a.chunks_exact(8);
+    let mut b_chunks = b.chunks_exact(8);
+    for (x, y) in (&mut a_chunks).zip(&mut b_chunks) {
+        let (x, y) = (read_u64(x), read_u64(y));
+        ab += u64::from((x & y).count_ones());
+        aa += u64::from(x.count_ones());
+        bb += u64::from(y.count_ones());
+    }
+    for (x, y) in a_chunks.remainder().iter().zip(b_chunks.remainder()) {
+        ab += u64::from((x & y).count_ones());
+        aa += u64::from(x.count_ones());
+        bb += u64::from(y.count_ones());
+    }
+    (ab, aa, bb)
+}
+
+fn read_u64(chunk: &[u8]) -> u64 {
+    u64::from_ne_bytes(chunk.try_into().unwrap())
+}
+
+#[cfg(feature = "halfvec")]
+fn same_len<'a>(a: &'a [f16], b: &'a [f16]) -> (&'a [f16], &'a [f16]) {
+    let n = a.len().min(b.len());
+    (&a[..n], &b[..n])
+}
+
+fn cosine(similarity: f32, norma: f32, normb: f32) -> f64 {
+    // use sqrt(a * b) over sqrt(a) * sqrt(b)
+    f64::from(similarity) / (f64::from(norma) * f64::from(normb)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distance::fixtures::{assert_close, values};
+    use crate::distance::scalar;
+
+    const LENGTHS: [usize; 8] = [0, 1, 7, 8, 15, 17, 100, 1536];
+
+    #[test]
+    fn test_f32() {
+        for n in LENGTHS {
+            let a = values(n, 1);
+            let b = values(n, 2);
+            let mut kernels = Vec::new();
+            if has_avx512() {
+                kernels.push(unsafe {
+                    [
+                        l2_squared_distance_avx512(&a, &b).into(),
+                        inner_product_avx512(&a, &b).into(),
+                        cosine_similarity_avx512(&a, &b),
+                        l1_distance_avx512(&a, &b).into(),
+                    ]
+                });
+            }
+            if has_avx2() {
+                kernels.push(unsafe {
+                    [
+                        l2_squared_distance_avx2(&a, &b).into(),
+                        inner_product_avx2(&a, &b).into(),
+                        cosine_similarity_avx2(&a, &b),
+                        l1_distance_avx2(&a, &b).into(),
+                    ]
+                });
+            }
+            for results in kernels {
+                assert_close(scalar::l2_squared_distance(&a, &b).into(), results[0]);
+                assert_close(scalar::inner_product(&a, &b).into(), results[1]);
+                let cosine = scalar::cosine_similarity(&a, &b);
+                if cosine.is_nan() {
+                    assert!(results[2].is_nan());
+                } else {
+                    assert_close(cosine, results[2]);
+                }
+                assert_close(scalar::l1_distance(&a, &b).into(), results[3]);
+            }
+        }
+    }
+
+    #[cfg(feature = "halfvec")]
+    #[test]
+    fn test_half() {
+        for n in LENGTHS {
+            let a: Vec<f16> = values(n, 3).into_iter().map(f16::from_f32).collect();
+            let b: Vec<f16> = values(n, 4).into_iter().map(f16::from_f32).collect();
+            let mut kernels = Vec::new();
+            if has_avx512() {
+                kernels.push(unsafe {
+                    [
+                        half_l2_squared_distance_avx512(&a, &b).into(),
+                        half_inner_product_avx512(&a, &b).into(),
+                        half_cosine_similarity_avx512(&a, &b),
+                        half_l1_distance_avx512(&a, &b).into(),
+                    ]
+                });
+            }
+            if has_f16c() {
+                kernels.push(unsafe {
+                    [
+                        half_l2_squared_distance_f16c(&a, &b).into(),
+                        half_inner_product_f16c(&a, &b).into(),
+                        half_cosine_similarity_f16c(&a, &b),
+                        half_l1_distance_f16c(&a, &b).into(),
+                    ]
+                });
+            }
+            for results in kernels {
+                assert_close(scalar::half_l2_squared_distance(&a, &b).into(), results[0]);
+                assert_close(scalar::half_inner_product(&a, &b).into(), results[1]);
+                let cosine = scalar::half_cosine_similarity(&a, &b);
+                if cosine.is_nan() {
+                    assert!(results[2].is_nan());
+                } else {
+                    assert_close(cosine, results[2]);
+                }
+                assert_close(scalar::half_l1_distance(&a, &b).into(), results[3]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sparse_inner_product() {
+        let a_indices: Vec<i32> = (0..500).map(|i| i * 3).collect();
+        let b_indices: Vec<i32> = (0..300).map(|i| i * 5 + 1).collect();
+        let a_values = values(a_indices.len(), 5);
+        let b_values = values(b_indices.len(), 6);
+        let expected = scalar::sparse_inner_product(&a_indices, &a_values, &b_indices, &b_values);
+
+        // the dispatcher swaps the arguments, which does not change the order of additions
+        if let Some(result) = sparse_inner_product(&b_indices, &b_values, &a_indices, &a_values) {
+            assert_eq!(expected, result);
+        }
+        if has_avx512() {
+            let result = unsafe {
+                sparse_inner_product_avx512(&a_indices, &a_values, &b_indices, &b_values)
+            };
+            assert_eq!(expected, result);
+        }
+        if has_avx2() {
+            let result =
+                unsafe { sparse_inner_product_avx2(&a_indices, &a_values, &b_indices, &b_values) };
+            assert_eq!(expected, result);
+        }
+    }
+
+    #[test]
+    fn test_bit() {
+        for n in [0, 1, 7, 8, 63, 64, 65, 200] {
+            let a: Vec<u8> = (0..n).map(|i| (i * 37 + 11) as u8).collect();
+            let b: Vec<u8> = (0..n).map(|i| (i * 91 + 5) as u8).collect();
+            let hamming = scalar::hamming_distance(&a, &b);
+            let jaccard = scalar::jaccard_counts(&a, &b);
+            if is_x86_feature_detected!("avx512vpopcntdq") {
+                assert_eq!(hamming, unsafe { hamming_distance_avx512(&a, &b) });
+                assert_eq!(jaccard, unsafe { jaccard_counts_avx512(&a, &b) });
+            }
+            if is_x86_feature_detected!("popcnt") {
+                assert_eq!(hamming, unsafe { hamming_distance_popcnt(&a, &b) });
+                assert_eq!(jaccard, unsafe { jaccard_counts_popcnt(&a, &b) });
+            }
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use std::fmt;
+
+/// An error.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The text representation is invalid.
+    InvalidSyntax { type_name: &'static str },
+    /// A value is out of range for the type.
+    OutOfRange {
+        type_name: &'static str,
+        value: String,
+    },
+    /// A value is NaN.
+    NaN { type_name: &'static str },
+    /// A value is infinite.
+    Infinite { type_name: &'static str },
+    /// There are no dimensions.
+    TooFewDimensions { type_name: &'static str },
+    /// There are more dimensions than the type allows.
+    TooManyDimensions { type_name: &'static str, max: usize },
+    /// There are more non-zero elements than a sparse vector allows.
+    TooManyNonZero { max: usize },
+    /// A sparse index is negative or not less than the number of dimensions.
+    IndexOutOfBounds { index: i64, dim: i32 },
+    /// Sparse indices are not in ascending order.
+    UnsortedIndices,
+    /// Sparse indices contain a duplicate.
+    DuplicateIndex { index: i32 },
+    /// A sparse vector has a different number of indices and values.
+    MismatchedLengths { indices: usize, values: usize },
+    /// A sparse vector contains a zero value.
+    ZeroValue { index: i32 },
+    /// A vector has a different number of dimensions than expected.
+    UnexpectedDimensions { expected: usize, actual: usize },
+    /// Two vectors have different dimensions.
+    DifferentDimensions {
+        type_name: &'static str,
+        left: usize,
+        right: usize,
+    },
+    /// The result of an operation is too large for the type.
+    Overflow,
+    /// The result of an operation is too small for the type.
+    Underflow,
+    /// The binary representation is shorter than its header.
+    Truncated {
+        type_name: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    /// The binary representation has a different length than its header specifies.
+    InvalidLength {
+        type_name: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    /// The reserved field of the binary representation is not zero.
+    NonZeroUnused { type_name: &'static str, value: i32 },
+    /// The binary representation has a negative or overflowing length.
+    DimensionOverflow { type_name: &'static str, value: i64 },
+    /// A substring length is negative.
+    NegativeSubstringLength,
+    /// A character is not a valid binary digit.
+    InvalidBinaryDigit(char),
+    /// A character is not a valid hexadecimal digit.
+    InvalidHexDigit(char),
+    /// An array has a data type that cannot be converted.
+    UnsupportedDataType {
+        type_name: &'static str,
+        data_type: String,
+    },
+    /// An array contains null elements.
+    NullElement { type_name: &'static str },
+    /// An array does not have two dimensions.
+    InvalidShape { ndim: usize },
+    /// A borrowed type cannot be decoded from the text format.
+    TextFormat { type_name: &'static str },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidSyntax { type_name } => {
+                write!(f, "invalid input syntax for type {}", type_name)
+            }
+            Error::OutOfRange { type_name, value } => {
+                write!(f, "\"{}\" is out of range for type {}", value, type_name)
+            }
+            Error::NaN { type_name } => write!(f, "NaN not allowed in {}", type_name),
+            Error::Infinite { type_name } => {
+                write!(f, "infinite value not allowed in {}", type_name)
+            }
+            Error::TooFewDimensions { type_name } => {
+                write!(f, "{} must have at least 1 dimension", type_name)
+            }
+            Error::TooManyDimensions {
+                type_name: "bit",
+                max,
+            } => write!(f, "bit string length exceeds the maximum allowed ({})", max),
+            Error::TooManyDimensions { type_name, max } => {
+                write!(f, "{} cannot have more than {} dimensions", type_name, max)
+            }
+            Error::TooManyNonZero { max } => {
+                write!(
+                    f,
+                    "sparsevec cannot have more than {} non-zero elements",
+                    max
+                )
+            }
+            Error::IndexOutOfBounds { index, .. } if *index < 0 => {
+                f.write_str("index must be greater than zero")
+            }
+            Error::IndexOutOfBounds { .. } => {
+                f.write_str("index must be less than or equal to dimensions")
+            }
+            Error::UnsortedIndices => f.write_str("indexes must be in ascending order"),
+            Error::DuplicateIndex { .. } => f.write_str("indexes must not contain duplicates"),
+            Error::MismatchedLengths { indices, values } => {
+                write!(f, "sparsevec has {} indices but {} values", indices, values)
+            }
+            Error::ZeroValue { .. } => f.write_str("sparsevec cannot contain zero values"),
+            Error::UnexpectedDimensions { expected, actual } => {
+                write!(f, "expected {} dimensions, not {}", expected, actual)
+            }
+            Error::DifferentDimensions {
+                type_name: "bit",
+                left,
+                right,
+            } => write!(f, "different bit lengths {} and {}", left, right),
+            Error::DifferentDimensions {
+                type_name,
+                left,
+                right,
+            } => write!(
+                f,
+                "different {} dimensions {} and {}",
+                type_name, left, right
+            ),
+            Error::Overflow => f.write_str("value out of range: overflow"),
+            Error::Underflow => f.write_str("value out of range: underflow"),
+            Error::Truncated {
+                type_name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "truncated binary representation of {}: expected at least {} bytes, got {}",
+                type_name, expected, actual
+            ),
+            Error::InvalidLength {
+                type_name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "invalid binary representation of {}: expected {} bytes, got {}",
+                type_name, expected, actual
+            ),
+            Error::NonZeroUnused { type_name, value } => write!(
+                f,
+                "invalid binary representation of {}: expected unused to be 0, got {}",
+                type_name, value
+            ),
+            Error::DimensionOverflow { type_name, value } => write!(
+                f,
+                "invalid binary representation of {}: length {} is out of range",
+                type_name, value
+            ),
+            Error::NegativeSubstringLength => f.write_str("negative substring length not allowed"),
+            Error::InvalidBinaryDigit(c) => write!(f, "\"{}\" is not a valid binary digit", c),
+            Error::InvalidHexDigit(c) => {
+                write!(f, "\"{}\" is not a valid hexadecimal digit", c)
+            }
+            Error::UnsupportedDataType {
+                type_name,
+                data_type,
+            } => write!(f, "cannot convert {} to {}", data_type, type_name),
+            Error::NullElement { .. } => f.write_str("array must not contain nulls"),
+            Error::InvalidShape { ndim } => {
+                write!(
+                    f,
+                    "expected a 2-dimensional array, not {}-dimensional",
+                    ndim
+                )
+            }
+            Error::TextFormat { type_name } => {
+                write!(
+                    f,
+                    "borrowed {} cannot be decoded from the text format",
+                    type_name
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,156 @@
+#[cfg(feature = "diesel")]
+use crate::diesel_ext::halfvec::HalfVectorType;
+
+#[cfg(feature = "diesel")]
+use diesel::{deserialize::FromSqlRow, expression::AsExpression};
+
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use half::f16;
+
+use crate::{Error, HalfVector};
+
+#[cfg(any(feature = "postgres", feature = "sqlx", feature = "diesel"))]
+use crate::HalfVectorRef;
+
+/// A half vector with a fixed number of dimensions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "diesel", derive(FromSqlRow, AsExpression))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = HalfVectorType))]
+pub struct FixedHalfVector<const N: usize>(pub(crate) [f16; N]);
+
+impl<const N: usize> From<[f16; N]> for FixedHalfVector<N> {
+    fn from(v: [f16; N]) -> Self {
+        FixedHalfVector(v)
+    }
+}
+
+impl<const N: usize> From<FixedHalfVector<N>> for [f16; N] {
+    fn from(val: FixedHalfVector<N>) -> Self {
+        val.0
+    }
+}
+
+impl<const N: usize> From<FixedHalfVector<N>> for HalfVector {
+    fn from(val: FixedHalfVector<N>) -> Self {
+        HalfVector::from(val.0.to_vec())
+    }
+}
+
+impl<const N: usize> TryFrom<&[f16]> for FixedHalfVector<N> {
+    type Error = Error;
+
+    fn try_from(v: &[f16]) -> Result<Self, Self::Error> {
+        let v = v.try_into().map_err(|_| Error::UnexpectedDimensions {
+            expected: N,
+            actual: v.len(),
+        })?;
+        Ok(FixedHalfVector(v))
+    }
+}
+
+impl<const N: usize> TryFrom<&HalfVector> for FixedHalfVector<N> {
+    type Error = Error;
+
+    fn try_from(v: &HalfVector) -> Result<Self, Self::Error> {
+        FixedHalfVector::try_from(v.as_slice())
+    }
+}
+
+impl<const N: usize> TryFrom<HalfVector> for FixedHalfVector<N> {
+    type Error = Error;
+
+    fn try_from(v: HalfVector) -> Result<Self, Self::Error> {
+        FixedHalfVector::try_from(v.as_slice())
+    }
+}
+
+impl<const N: usize> FixedHalfVector<N> {
+    /// Returns the half vector as a slice.
+    pub fn as_slice(&self) -> &[f16] {
+        &self.0
+    }
+
+    /// Returns the half vector as an array.
+    pub fn as_array(&self) -> &[f16; N] {
+        &self.0
+    }
+
+    #[cfg(any(feature = "postgres", feature = "sqlx", feature = "diesel"))]
+    pub(crate) fn from_sql(buf: &[u8]) -> Result<FixedHalfVector<N>, Error> {
+        let vec = HalfVectorRef::from_sql(buf)?;
+        if vec.len() != N {
+            return Err(Error::UnexpectedDimensions {
+                expected: N,
+                actual: vec.len(),
+            });
+        }
+        let mut arr = [f16::ZERO; N];
+        vec.copy_to_slice(&mut arr);
+        Ok(FixedHalfVector(arr))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const N: usize> Serialize for FixedHalfVector<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(|v| v.to_f32()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> Deserialize<'de> for FixedHalfVector<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let vec = HalfVector::deserialize(deserializer)?;
+        FixedHalfVector::try_from(vec).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, FixedHalfVector, HalfVector};
+
+    #[test]
+    fn test_half_vector() {
+        let vec = HalfVector::from_f32_slice(&[1.0, 2.0, 3.0]);
+        let fixed_vec = FixedHalfVector::<3>::try_from(&vec).unwrap();
+        assert_eq!(vec.as_slice(), fixed_vec.as_slice());
+        assert_eq!(vec, HalfVector::from(fixed_vec));
+
+        assert_eq!(
+            Err(Error::UnexpectedDimensions {
+                expected: 2,
+                actual: 3
+            }),
+            FixedHalfVector::<2>::try_from(vec)
+        );
+    }
+
+    #[cfg(any(feature = "postgres", feature = "sqlx", feature = "diesel"))]
+    #[test]
+    fn test_from_sql() {
+        use half::f16;
+
+        let buf = [0, 2, 0, 0, 60, 0, 64, 0];
+        assert_eq!(
+            FixedHalfVector::from([f16::from_f32(1.0), f16::from_f32(2.0)]),
+            FixedHalfVector::<2>::from_sql(&buf).unwrap()
+        );
+        assert!(FixedHalfVector::<1>::from_sql(&buf).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        use half::f16;
+
+        let vec = FixedHalfVector::from([f16::from_f32(1.5); 2]);
+        let json = serde_json::to_string(&vec).unwrap();
+        assert_eq!("[1.5,1.5]", json);
+        assert_eq!(vec, serde_json::from_str(&json).unwrap());
+
+        let err = serde_json::from_str::<FixedHalfVector<3>>("[1.0,2.0]").unwrap_err();
+        assert_eq!("expected 3 dimensions, not 2", err.to_string());
+    }
+}
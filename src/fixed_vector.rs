@@ -0,0 +1,163 @@
+#[cfg(feature = "diesel")]
+use crate::diesel_ext::vector::VectorType;
+
+#[cfg(feature = "diesel")]
+use diesel::{deserialize::FromSqlRow, expression::AsExpression};
+
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Error, Vector};
+
+#[cfg(any(feature = "postgres", feature = "sqlx", feature = "diesel"))]
+use crate::VectorRef;
+
+/// A vector with a fixed number of dimensions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "diesel", derive(FromSqlRow, AsExpression))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = VectorType))]
+pub struct FixedVector<const N: usize>(pub(crate) [f32; N]);
+
+impl<const N: usize> From<[f32; N]> for FixedVector<N> {
+    fn from(v: [f32; N]) -> Self {
+        FixedVector(v)
+    }
+}
+
+impl<const N: usize> From<FixedVector<N>> for [f32; N] {
+    fn from(val: FixedVector<N>) -> Self {
+        val.0
+    }
+}
+
+impl<const N: usize> From<FixedVector<N>> for Vector {
+    fn from(val: FixedVector<N>) -> Self {
+        Vector::from(val.0.to_vec())
+    }
+}
+
+impl<const N: usize> TryFrom<&[f32]> for FixedVector<N> {
+    type Error = Error;
+
+    fn try_from(v: &[f32]) -> Result<Self, Self::Error> {
+        let v = v.try_into().map_err(|_| Error::UnexpectedDimensions {
+            expected: N,
+            actual: v.len(),
+        })?;
+        Ok(FixedVector(v))
+    }
+}
+
+impl<const N: usize> TryFrom<&Vector> for FixedVector<N> {
+    type Error = Error;
+
+    fn try_from(v: &Vector) -> Result<Self, Self::Error> {
+        FixedVector::try_from(v.as_slice())
+    }
+}
+
+impl<const N: usize> TryFrom<Vector> for FixedVector<N> {
+    type Error = Error;
+
+    fn try_from(v: Vector) -> Result<Self, Self::Error> {
+        FixedVector::try_from(v.as_slice())
+    }
+}
+
+impl<const N: usize> FixedVector<N> {
+    /// Returns the vector as a slice.
+    pub fn as_slice(&self) -> &[f32] {
+        &self.0
+    }
+
+    /// Returns the vector as an array.
+    pub fn as_array(&self) -> &[f32; N] {
+        &self.0
+    }
+
+    #[cfg(any(feature = "postgres", feature = "sqlx", feature = "diesel"))]
+    pub(crate) fn from_sql(buf: &[u8]) -> Result<FixedVector<N>, Error> {
+        let vec = VectorRef::from_sql(buf)?;
+        if vec.len() != N {
+            return Err(Error::UnexpectedDimensions {
+                expected: N,
+                actual: vec.len(),
+            });
+        }
+        let mut arr = [0.0; N];
+        vec.copy_to_slice(&mut arr);
+        Ok(FixedVector(arr))
+    }
+}
+
+// serde only implements traits for arrays up to 32 elements
+#[cfg(feature = "serde")]
+impl<const N: usize> Serialize for FixedVector<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.as_slice().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> Deserialize<'de> for FixedVector<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let vec = Vec::<f32>::deserialize(deserializer)?;
+        FixedVector::try_from(vec.as_slice()).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, FixedVector, Vector};
+
+    #[test]
+    fn test_into() {
+        let vec = FixedVector::from([1.0, 2.0, 3.0]);
+        let arr: [f32; 3] = vec.into();
+        assert_eq!([1.0, 2.0, 3.0], arr);
+    }
+
+    #[test]
+    fn test_vector() {
+        let vec = Vector::from(vec![1.0, 2.0, 3.0]);
+        let fixed_vec = FixedVector::<3>::try_from(&vec).unwrap();
+        assert_eq!(&[1.0, 2.0, 3.0], fixed_vec.as_array());
+        assert_eq!(vec, Vector::from(fixed_vec));
+
+        assert_eq!(
+            Err(Error::UnexpectedDimensions {
+                expected: 4,
+                actual: 3
+            }),
+            FixedVector::<4>::try_from(vec)
+        );
+    }
+
+    #[cfg(any(feature = "postgres", feature = "sqlx", feature = "diesel"))]
+    #[test]
+    fn test_from_sql() {
+        let buf = [0, 2, 0, 0, 63, 128, 0, 0, 64, 0, 0, 0];
+        assert_eq!(
+            FixedVector::from([1.0, 2.0]),
+            FixedVector::<2>::from_sql(&buf).unwrap()
+        );
+        assert_eq!(
+            Err(Error::UnexpectedDimensions {
+                expected: 3,
+                actual: 2
+            }),
+            FixedVector::<3>::from_sql(&buf)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let vec = FixedVector::from([1.0; 33]);
+        let json = serde_json::to_string(&vec).unwrap();
+        assert_eq!(vec, serde_json::from_str(&json).unwrap());
+
+        let err = serde_json::from_str::<FixedVector<3>>("[1.0,2.0]").unwrap_err();
+        assert_eq!("expected 3 dimensions, not 2", err.to_string());
+    }
+}
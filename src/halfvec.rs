@@ -6,6 +6,23 @@ use crate::diesel_ext::halfvec::HalfVectorType;
 #[cfg(feature = "diesel")]
 use diesel::{deserialize::FromSqlRow, expression::AsExpression};
 
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::distance;
+use crate::limits;
+use crate::ops;
+use crate::text;
+use crate::{Bit, Error, SparseVector, Vector};
+
+#[cfg(any(feature = "postgres", feature = "sqlx", feature = "diesel"))]
+use crate::HalfVectorRef;
+
+pub(crate) const HALFVEC_MAX_DIM: usize = 16000;
+
 /// A half vector.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "diesel", derive(FromSqlRow, AsExpression))]
@@ -25,6 +42,15 @@ impl From<HalfVector> for Vec<f16> {
 }
 
 impl HalfVector {
+    /// Creates a half vector, checking the same limits as the server.
+    pub fn try_new(vec: Vec<f16>) -> Result<HalfVector, Error> {
+        limits::check_dims(vec.len(), "halfvec", HALFVEC_MAX_DIM)?;
+        for v in &vec {
+            text::check_element(v.to_f32(), "halfvec")?;
+        }
+        Ok(HalfVector(vec))
+    }
+
     /// Creates a half vector from a `f32` slice.
     pub fn from_f32_slice(slice: &[f32]) -> HalfVector {
         HalfVector(slice.iter().map(|v| f16::from_f32(*v)).collect())
@@ -40,39 +66,313 @@ impl HalfVector {
         self.0.as_slice()
     }
 
-    #[cfg(any(feature = "postgres", feature = "sqlx", feature = "diesel"))]
-    pub(crate) fn from_sql(
-        buf: &[u8],
-    ) -> Result<HalfVector, Box<dyn std::error::Error + Sync + Send>> {
-        if buf.len() < 4 {
-            return Err("invalid length".into());
+    /// Returns the number of dimensions.
+    pub fn dimensions(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns the Euclidean norm, like the `l2_norm` function.
+    pub fn norm(&self) -> f64 {
+        self.0
+            .iter()
+            .map(|v| {
+                let v = f64::from(v.to_f32());
+                v * v
+            })
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// Returns the halfvec normalized to unit length, like the `l2_normalize` function.
+    ///
+    /// A zero halfvec stays zero.
+    pub fn l2_normalize(&self) -> HalfVector {
+        let norm = self.norm();
+        if norm > 0.0 {
+            HalfVector(
+                self.0
+                    .iter()
+                    .map(|v| f16::from_f32((f64::from(v.to_f32()) / norm) as f32))
+                    .collect(),
+            )
+        } else {
+            HalfVector(vec![f16::ZERO; self.0.len()])
         }
+    }
+
+    /// Returns `count` elements starting at the 1-based position `start`, like the `subvector` function.
+    pub fn subvector(&self, start: i32, count: i32) -> Result<HalfVector, Error> {
+        let range = ops::subvector_range(self.0.len(), start, count, "halfvec")?;
+        Ok(HalfVector(self.0[range].to_vec()))
+    }
 
-        let dim = u16::from_be_bytes(buf[0..2].try_into()?).into();
-        let unused = u16::from_be_bytes(buf[2..4].try_into()?);
-        if unused != 0 {
-            return Err("expected unused to be 0".into());
+    /// Returns a bit string with the sign of each element, like the `binary_quantize` function.
+    pub fn binary_quantize(&self) -> Bit {
+        Bit::from_bits(self.0.iter().map(|v| v.to_f32() > 0.0))
+    }
+
+    /// Returns the L2 distance to another halfvec, like the `<->` operator.
+    pub fn l2_distance(&self, other: &HalfVector) -> Result<f64, Error> {
+        self.check_dims(other)?;
+        let distance = distance::half_l2_squared_distance(&self.0, &other.0);
+        Ok(f64::from(distance).sqrt())
+    }
+
+    /// Returns the inner product with another halfvec.
+    pub fn inner_product(&self, other: &HalfVector) -> Result<f64, Error> {
+        self.check_dims(other)?;
+        Ok(f64::from(distance::half_inner_product(&self.0, &other.0)))
+    }
+
+    /// Returns the negative inner product with another halfvec, like the `<#>` operator.
+    pub fn max_inner_product(&self, other: &HalfVector) -> Result<f64, Error> {
+        self.inner_product(other).map(|v| -v)
+    }
+
+    /// Returns the cosine distance to another halfvec, like the `<=>` operator.
+    pub fn cosine_distance(&self, other: &HalfVector) -> Result<f64, Error> {
+        self.check_dims(other)?;
+        let similarity = distance::half_cosine_similarity(&self.0, &other.0);
+        Ok(distance::cosine_distance(similarity))
+    }
+
+    /// Returns the L1 distance to another halfvec, like the `<+>` operator.
+    pub fn l1_distance(&self, other: &HalfVector) -> Result<f64, Error> {
+        self.check_dims(other)?;
+        Ok(f64::from(distance::half_l1_distance(&self.0, &other.0)))
+    }
+
+    /// Concatenates with another halfvec, like the `||` operator.
+    pub fn concat(&self, other: &HalfVector) -> Result<HalfVector, Error> {
+        if self.0.len() + other.0.len() > HALFVEC_MAX_DIM {
+            return Err(Error::TooManyDimensions {
+                type_name: "halfvec",
+                max: HALFVEC_MAX_DIM,
+            });
         }
+        Ok(HalfVector([self.as_slice(), other.as_slice()].concat()))
+    }
 
-        if (buf.len() - 4) / 2 != dim || buf.len() % 2 != 0 {
-            return Err("invalid length".into());
+    fn try_add(&self, other: &HalfVector) -> Result<HalfVector, Error> {
+        self.zip_with(other, |a, b| ops::check_result(round(a + b)))
+    }
+
+    fn try_sub(&self, other: &HalfVector) -> Result<HalfVector, Error> {
+        self.zip_with(other, |a, b| ops::check_result(round(a - b)))
+    }
+
+    fn try_mul(&self, other: &HalfVector) -> Result<HalfVector, Error> {
+        self.zip_with(other, |a, b| ops::check_product(round(a * b), a, b))
+    }
+
+    fn try_scale(&self, scalar: f16) -> Result<HalfVector, Error> {
+        let b = scalar.to_f32();
+        self.0
+            .iter()
+            .map(|a| {
+                let a = a.to_f32();
+                ops::check_product(round(a * b), a, b).map(f16::from_f32)
+            })
+            .collect::<Result<_, _>>()
+            .map(HalfVector)
+    }
+
+    // computes in f32 like the server
+    fn zip_with<F: Fn(f32, f32) -> Result<f32, Error>>(
+        &self,
+        other: &HalfVector,
+        f: F,
+    ) -> Result<HalfVector, Error> {
+        self.check_dims(other)?;
+        self.0
+            .iter()
+            .zip(&other.0)
+            .map(|(a, b)| f(a.to_f32(), b.to_f32()).map(f16::from_f32))
+            .collect::<Result<_, _>>()
+            .map(HalfVector)
+    }
+
+    fn check_dims(&self, other: &HalfVector) -> Result<(), Error> {
+        if self.0.len() != other.0.len() {
+            return Err(Error::DifferentDimensions {
+                type_name: "halfvec",
+                left: self.0.len(),
+                right: other.0.len(),
+            });
         }
+        Ok(())
+    }
+
+    #[cfg(any(feature = "postgres", feature = "sqlx", feature = "diesel"))]
+    pub(crate) fn from_sql(buf: &[u8]) -> Result<HalfVector, Error> {
+        Ok(HalfVectorRef::from_sql(buf)?.to_half_vector())
+    }
+}
+
+ops::impl_ops!(HalfVector, f16);
+
+// serializes as floats since half does not enable serde
+#[cfg(feature = "serde")]
+impl Serialize for HalfVector {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(|v| v.to_f32()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for HalfVector {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let vec = Vec::<f32>::deserialize(deserializer)?;
+        vec.into_iter()
+            .map(to_half)
+            .collect::<Result<_, _>>()
+            .map(HalfVector)
+            .map_err(de::Error::custom)
+    }
+}
+
+// rounds to the nearest half
+fn round(v: f32) -> f32 {
+    f16::from_f32(v).to_f32()
+}
+
+// converts to a half like the server, which only errors on finite values
+pub(crate) fn to_half(v: f32) -> Result<f16, Error> {
+    let h = f16::from_f32(v);
+    if h.is_infinite() && !v.is_infinite() {
+        let mut value = String::new();
+        text::write_float(&mut value, v).unwrap();
+        return Err(Error::OutOfRange {
+            type_name: "halfvec",
+            value,
+        });
+    }
+    Ok(h)
+}
+
+impl From<&HalfVector> for Vector {
+    fn from(vec: &HalfVector) -> Self {
+        Vector::from(vec.0.iter().map(|v| v.to_f32()).collect::<Vec<f32>>())
+    }
+}
+
+impl From<HalfVector> for Vector {
+    fn from(vec: HalfVector) -> Self {
+        Vector::from(&vec)
+    }
+}
+
+impl TryFrom<&Vector> for HalfVector {
+    type Error = Error;
 
-        let mut vec = Vec::with_capacity(dim);
-        for i in 0..dim {
-            let s = 4 + 2 * i;
-            vec.push(f16::from_be_bytes(buf[s..s + 2].try_into()?));
+    fn try_from(vec: &Vector) -> Result<Self, Self::Error> {
+        limits::check_dims(vec.as_slice().len(), "halfvec", HALFVEC_MAX_DIM)?;
+        vec.as_slice()
+            .iter()
+            .map(|v| to_half(*v))
+            .collect::<Result<_, _>>()
+            .map(HalfVector)
+    }
+}
+
+impl TryFrom<Vector> for HalfVector {
+    type Error = Error;
+
+    fn try_from(vec: Vector) -> Result<Self, Self::Error> {
+        HalfVector::try_from(&vec)
+    }
+}
+
+impl TryFrom<&HalfVector> for SparseVector {
+    type Error = Error;
+
+    fn try_from(vec: &HalfVector) -> Result<Self, Self::Error> {
+        SparseVector::try_from_dense(vec.0.iter().map(|v| v.to_f32()))
+    }
+}
+
+impl TryFrom<HalfVector> for SparseVector {
+    type Error = Error;
+
+    fn try_from(vec: HalfVector) -> Result<Self, Self::Error> {
+        SparseVector::try_from(&vec)
+    }
+}
+
+impl TryFrom<&SparseVector> for HalfVector {
+    type Error = Error;
+
+    fn try_from(vec: &SparseVector) -> Result<Self, Self::Error> {
+        vec.try_to_dense("halfvec", HALFVEC_MAX_DIM)?
+            .into_iter()
+            .map(to_half)
+            .collect::<Result<_, _>>()
+            .map(HalfVector)
+    }
+}
+
+impl TryFrom<SparseVector> for HalfVector {
+    type Error = Error;
+
+    fn try_from(vec: SparseVector) -> Result<Self, Self::Error> {
+        HalfVector::try_from(&vec)
+    }
+}
+
+impl fmt::Display for HalfVector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        for (i, v) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            text::write_float(f, v.to_f32())?;
         }
+        f.write_str("]")
+    }
+}
+
+impl FromStr for HalfVector {
+    type Err = Error;
 
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let vec = text::parse_array(s, "halfvec", HALFVEC_MAX_DIM, |scanner| {
+            let start = scanner.position();
+            let v = scanner.float("halfvec")?;
+            let h = f16::from_f32(v);
+            if h.is_infinite() && !v.is_infinite() {
+                return Err(Error::OutOfRange {
+                    type_name: "halfvec",
+                    value: scanner.since(start).to_string(),
+                });
+            }
+            text::check_element(h.to_f32(), "halfvec")?;
+            Ok(h)
+        })?;
         Ok(HalfVector(vec))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::HalfVector;
+    use crate::{Error, HalfVector, SparseVector, Vector};
     use half::f16;
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let vec = HalfVector::from_f32_slice(&[1.0, 2.5, 3.0]);
+        let json = serde_json::to_string(&vec).unwrap();
+        assert_eq!("[1.0,2.5,3.0]", json);
+        assert_eq!(vec, serde_json::from_str(&json).unwrap());
+
+        let err = serde_json::from_str::<HalfVector>("[65520.0]").unwrap_err();
+        assert_eq!(
+            "\"65520\" is out of range for type halfvec",
+            err.to_string()
+        );
+    }
+
     #[test]
     fn test_into() {
         let vec = HalfVector::from(vec![
@@ -104,4 +404,271 @@ mod tests {
             &[f16::from_f32(1.0), f16::from_f32(2.0), f16::from_f32(3.0)]
         );
     }
+
+    #[test]
+    fn test_display() {
+        let vec = HalfVector::from_f32_slice(&[1.0, -2.5, 0.1]);
+        assert_eq!("[1,-2.5,0.099975586]", vec.to_string());
+    }
+
+    #[test]
+    fn test_from_str() {
+        let vec: HalfVector = "[1,2,3]".parse().unwrap();
+        assert_eq!(HalfVector::from_f32_slice(&[1.0, 2.0, 3.0]), vec);
+
+        let vec: HalfVector = " [ 1.5 , -2e3 ] ".parse().unwrap();
+        assert_eq!(HalfVector::from_f32_slice(&[1.5, -2000.0]), vec);
+    }
+
+    #[test]
+    fn test_from_str_round_trip() {
+        let vec = HalfVector::from_f32_slice(&[0.1, 1.0 / 3.0, 65504.0, 6e-8]);
+        assert_eq!(vec, vec.to_string().parse().unwrap());
+    }
+
+    #[test]
+    fn test_from_str_invalid() {
+        assert_eq!(
+            Err(Error::InvalidSyntax {
+                type_name: "halfvec"
+            }),
+            "[1,2".parse::<HalfVector>()
+        );
+        assert_eq!(
+            Err(Error::TooFewDimensions {
+                type_name: "halfvec"
+            }),
+            "[ ]".parse::<HalfVector>()
+        );
+        assert_eq!(
+            Err(Error::OutOfRange {
+                type_name: "halfvec",
+                value: "65520".to_string()
+            }),
+            "[65520]".parse::<HalfVector>()
+        );
+        assert_eq!(
+            Err(Error::Infinite {
+                type_name: "halfvec"
+            }),
+            "[inf]".parse::<HalfVector>()
+        );
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = HalfVector::from_f32_slice(&[1.0, 1.0, 1.0]);
+        let b = HalfVector::from_f32_slice(&[1.0, 1.0, 2.0]);
+        assert_eq!(1.0, a.l2_distance(&b).unwrap());
+        assert_eq!(4.0, a.inner_product(&b).unwrap());
+        assert_eq!(-4.0, a.max_inner_product(&b).unwrap());
+        assert!((a.cosine_distance(&b).unwrap() - 0.057190958417936644).abs() < 1e-9);
+        assert_eq!(1.0, a.l1_distance(&b).unwrap());
+    }
+
+    #[test]
+    fn test_distances_accumulate_in_f32() {
+        // the squares overflow f16 but not f32
+        let a = HalfVector::from_f32_slice(&[60000.0, 60000.0]);
+        let b = HalfVector::from_f32_slice(&[0.0, 0.0]);
+        assert_eq!(7.2e9, a.inner_product(&a).unwrap());
+        assert_eq!(7.2e9_f64.sqrt(), a.l2_distance(&b).unwrap());
+    }
+
+    #[test]
+    fn test_distance_different_dimensions() {
+        let a = HalfVector::from_f32_slice(&[1.0, 2.0]);
+        let b = HalfVector::from_f32_slice(&[1.0, 2.0, 3.0]);
+        assert_eq!(
+            Err(Error::DifferentDimensions {
+                type_name: "halfvec",
+                left: 2,
+                right: 3
+            }),
+            a.cosine_distance(&b)
+        );
+    }
+
+    #[test]
+    fn test_ops() {
+        let a = HalfVector::from_f32_slice(&[1.0, 2.0, 3.0]);
+        let b = HalfVector::from_f32_slice(&[4.0, 5.0, 6.0]);
+        assert_eq!(
+            HalfVector::from_f32_slice(&[5.0, 7.0, 9.0]),
+            (&a + &b).unwrap()
+        );
+        assert_eq!(
+            HalfVector::from_f32_slice(&[-3.0, -3.0, -3.0]),
+            (&a - &b).unwrap()
+        );
+        assert_eq!(
+            HalfVector::from_f32_slice(&[4.0, 10.0, 18.0]),
+            (&a * &b).unwrap()
+        );
+        assert_eq!(
+            HalfVector::from_f32_slice(&[2.0, 4.0, 6.0]),
+            (a * f16::from_f32(2.0)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_assign_ops() {
+        let mut a = HalfVector::from_f32_slice(&[1.0, 2.0, 3.0]);
+        a += HalfVector::from_f32_slice(&[1.0, 1.0, 1.0]);
+        a *= f16::from_f32(0.5);
+        assert_eq!(HalfVector::from_f32_slice(&[1.0, 1.5, 2.0]), a);
+    }
+
+    #[test]
+    fn test_ops_errors() {
+        // overflows f16 but not f32
+        let a = HalfVector::from(vec![f16::MAX]);
+        assert_eq!(Err(Error::Overflow), &a + &a);
+        assert_eq!(Err(Error::Overflow), &a * f16::from_f32(2.0));
+
+        let a = HalfVector::from_f32_slice(&[1e-4]);
+        assert_eq!(Err(Error::Underflow), &a * &a);
+    }
+
+    #[test]
+    fn test_concat() {
+        let a = HalfVector::from_f32_slice(&[1.0, 2.0]);
+        let b = HalfVector::from_f32_slice(&[3.0]);
+        assert_eq!(
+            HalfVector::from_f32_slice(&[1.0, 2.0, 3.0]),
+            a.concat(&b).unwrap()
+        );
+
+        let a = HalfVector::from_f32_slice(&[1.0; 16000]);
+        assert_eq!(
+            Err(Error::TooManyDimensions {
+                type_name: "halfvec",
+                max: 16000
+            }),
+            a.concat(&b)
+        );
+    }
+
+    #[test]
+    fn test_norm() {
+        let vec = HalfVector::from_f32_slice(&[3.0, 4.0]);
+        assert_eq!(2, vec.dimensions());
+        assert_eq!(5.0, vec.norm());
+    }
+
+    #[test]
+    fn test_l2_normalize() {
+        let vec = HalfVector::from_f32_slice(&[3.0, 4.0]);
+        assert_eq!(HalfVector::from_f32_slice(&[0.6, 0.8]), vec.l2_normalize());
+
+        let vec = HalfVector::from_f32_slice(&[0.0, 0.0]);
+        assert_eq!(vec, vec.l2_normalize());
+    }
+
+    #[test]
+    fn test_subvector() {
+        let vec = HalfVector::from_f32_slice(&[1.0, 2.0, 3.0]);
+        assert_eq!(
+            HalfVector::from_f32_slice(&[2.0, 3.0]),
+            vec.subvector(2, 5).unwrap()
+        );
+        assert_eq!(
+            Err(Error::TooFewDimensions {
+                type_name: "halfvec"
+            }),
+            vec.subvector(4, 1)
+        );
+    }
+
+    #[test]
+    fn test_binary_quantize() {
+        let vec = HalfVector::from_f32_slice(&[1.0, -1.0, 0.0, 2.0]);
+        assert_eq!("1001", vec.binary_quantize().to_string());
+    }
+
+    #[test]
+    fn test_vector_conversions() {
+        let vec = Vector::from(vec![1.0, 2.5, -3.0]);
+        let half_vec = HalfVector::try_from(&vec).unwrap();
+        assert_eq!(HalfVector::from_f32_slice(&[1.0, 2.5, -3.0]), half_vec);
+        assert_eq!(vec, Vector::from(half_vec));
+
+        let vec = Vector::from(vec![1.0, 65520.0]);
+        assert_eq!(
+            Err(Error::OutOfRange {
+                type_name: "halfvec",
+                value: "65520".to_string()
+            }),
+            HalfVector::try_from(vec)
+        );
+
+        let vec = Vector::from(vec![]);
+        assert_eq!(
+            Err(Error::TooFewDimensions {
+                type_name: "halfvec"
+            }),
+            HalfVector::try_from(vec)
+        );
+    }
+
+    #[test]
+    fn test_sparse_vector_conversions() {
+        let vec = HalfVector::from_f32_slice(&[1.0, 0.0, 2.0]);
+        let sparse_vec = SparseVector::try_from(&vec).unwrap();
+        assert_eq!(SparseVector::from_dense(&[1.0, 0.0, 2.0]), sparse_vec);
+        assert_eq!(vec, HalfVector::try_from(sparse_vec).unwrap());
+
+        let vec = SparseVector::from_dense(&[1e6]);
+        assert_eq!(
+            Err(Error::OutOfRange {
+                type_name: "halfvec",
+                value: "1e+06".to_string()
+            }),
+            HalfVector::try_from(vec)
+        );
+
+        let vec = HalfVector::from(vec![]);
+        assert_eq!(
+            Err(Error::TooFewDimensions {
+                type_name: "sparsevec"
+            }),
+            SparseVector::try_from(vec)
+        );
+
+        let vec = SparseVector::from_dense(&vec![0.0; 16001]);
+        assert_eq!(
+            Err(Error::TooManyDimensions {
+                type_name: "halfvec",
+                max: 16000
+            }),
+            HalfVector::try_from(vec)
+        );
+    }
+
+    #[test]
+    fn test_try_new() {
+        let vec = vec![f16::from_f32(1.0), f16::from_f32(2.0)];
+        assert_eq!(
+            HalfVector::from(vec.clone()),
+            HalfVector::try_new(vec).unwrap()
+        );
+        assert_eq!(
+            Err(Error::TooFewDimensions {
+                type_name: "halfvec"
+            }),
+            HalfVector::try_new(vec![])
+        );
+        assert_eq!(
+            Err(Error::NaN {
+                type_name: "halfvec"
+            }),
+            HalfVector::try_new(vec![f16::NAN])
+        );
+        assert_eq!(
+            Err(Error::Infinite {
+                type_name: "halfvec"
+            }),
+            HalfVector::try_new(vec![f16::NEG_INFINITY])
+        );
+    }
 }
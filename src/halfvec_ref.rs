@@ -0,0 +1,184 @@
+#[cfg(feature = "diesel")]
+use crate::diesel_ext::halfvec::HalfVectorType;
+
+#[cfg(feature = "diesel")]
+use diesel::expression::AsExpression;
+
+use half::f16;
+
+use crate::HalfVector;
+
+#[cfg(any(feature = "postgres", feature = "sqlx", feature = "diesel"))]
+use crate::Error;
+
+/// A borrowed half vector.
+///
+/// Borrows either a slice of values or the binary representation of a row, which is decoded lazily.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "diesel", derive(AsExpression))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = HalfVectorType))]
+pub struct HalfVectorRef<'a>(Repr<'a>);
+
+#[derive(Clone, Copy, Debug)]
+enum Repr<'a> {
+    Slice(&'a [f16]),
+    // big-endian values, only decoded by integrations
+    #[cfg_attr(
+        not(any(feature = "postgres", feature = "sqlx", feature = "diesel")),
+        allow(dead_code)
+    )]
+    Bytes(&'a [u8]),
+}
+
+impl<'a> From<&'a [f16]> for HalfVectorRef<'a> {
+    fn from(v: &'a [f16]) -> Self {
+        HalfVectorRef(Repr::Slice(v))
+    }
+}
+
+impl<'a> From<&'a HalfVector> for HalfVectorRef<'a> {
+    fn from(v: &'a HalfVector) -> Self {
+        HalfVectorRef(Repr::Slice(v.as_slice()))
+    }
+}
+
+impl<'a> HalfVectorRef<'a> {
+    /// Returns the number of dimensions.
+    pub fn len(&self) -> usize {
+        match self.0 {
+            Repr::Slice(v) => v.len(),
+            Repr::Bytes(v) => v.len() / 2,
+        }
+    }
+
+    /// Returns whether the half vector is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the values.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = f16> + 'a {
+        match self.0 {
+            Repr::Slice(v) => Iter::Slice(v.iter()),
+            Repr::Bytes(v) => Iter::Bytes(v.chunks_exact(2)),
+        }
+    }
+
+    /// Copies the values into a slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slice has a different length.
+    pub fn copy_to_slice(&self, dst: &mut [f16]) {
+        assert_eq!(
+            self.len(),
+            dst.len(),
+            "destination and source slices have different lengths"
+        );
+        match self.0 {
+            Repr::Slice(v) => dst.copy_from_slice(v),
+            Repr::Bytes(_) => {
+                for (d, v) in dst.iter_mut().zip(self.iter()) {
+                    *d = v;
+                }
+            }
+        }
+    }
+
+    /// Returns an owned half vector.
+    pub fn to_half_vector(&self) -> HalfVector {
+        HalfVector::from(self.iter().collect::<Vec<f16>>())
+    }
+
+    #[cfg(any(feature = "postgres", feature = "sqlx", feature = "diesel"))]
+    pub(crate) fn from_sql(buf: &'a [u8]) -> Result<HalfVectorRef<'a>, Error> {
+        if buf.len() < 4 {
+            return Err(Error::Truncated {
+                type_name: "halfvec",
+                expected: 4,
+                actual: buf.len(),
+            });
+        }
+
+        let dim = usize::from(u16::from_be_bytes([buf[0], buf[1]]));
+        let unused = u16::from_be_bytes([buf[2], buf[3]]);
+        if unused != 0 {
+            return Err(Error::NonZeroUnused {
+                type_name: "halfvec",
+                value: unused.into(),
+            });
+        }
+
+        let expected = 4 + 2 * dim;
+        if buf.len() != expected {
+            return Err(Error::InvalidLength {
+                type_name: "halfvec",
+                expected,
+                actual: buf.len(),
+            });
+        }
+
+        Ok(HalfVectorRef(Repr::Bytes(&buf[4..])))
+    }
+}
+
+impl PartialEq for HalfVectorRef<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+enum Iter<'a> {
+    Slice(std::slice::Iter<'a, f16>),
+    Bytes(std::slice::ChunksExact<'a, u8>),
+}
+
+impl Iterator for Iter<'_> {
+    type Item = f16;
+
+    fn next(&mut self) -> Option<f16> {
+        match self {
+            Iter::Slice(it) => it.next().copied(),
+            Iter::Bytes(it) => it.next().map(|v| f16::from_be_bytes([v[0], v[1]])),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Iter::Slice(it) => it.size_hint(),
+            Iter::Bytes(it) => it.size_hint(),
+        }
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{HalfVector, HalfVectorRef};
+    use half::f16;
+
+    #[test]
+    fn test_from_slice() {
+        let vec = HalfVector::from_f32_slice(&[1.0, 2.0, 3.0]);
+        let vec_ref = HalfVectorRef::from(&vec);
+        assert_eq!(3, vec_ref.len());
+        assert_eq!(vec.to_vec(), vec_ref.iter().collect::<Vec<f16>>());
+        assert_eq!(vec, vec_ref.to_half_vector());
+
+        let mut dst = [f16::ZERO; 3];
+        vec_ref.copy_to_slice(&mut dst);
+        assert_eq!(vec.as_slice(), dst);
+    }
+
+    #[cfg(any(feature = "postgres", feature = "sqlx", feature = "diesel"))]
+    #[test]
+    fn test_from_sql() {
+        let buf = [0, 2, 0, 0, 60, 0, 64, 0];
+        let vec = HalfVectorRef::from_sql(&buf).unwrap();
+        assert_eq!(
+            HalfVector::from_f32_slice(&[1.0, 2.0]),
+            vec.to_half_vector()
+        );
+    }
+}
@@ -1,22 +1,116 @@
 #![doc = include_str!("../README.md")]
 
 mod bit;
+mod distance;
+mod error;
+mod fixed_vector;
+mod limits;
+mod ops;
 mod sparsevec;
+mod sparsevec_builder;
+mod text;
 mod vector;
+mod vector_ref;
 
 pub use bit::Bit;
+pub use error::Error;
+pub use fixed_vector::FixedVector;
 pub use sparsevec::SparseVector;
+pub use sparsevec_builder::{DuplicatePolicy, SparseVectorBuilder};
 pub use vector::Vector;
+pub use vector_ref::VectorRef;
 
 #[cfg(feature = "halfvec")]
 mod halfvec;
 
+#[cfg(feature = "halfvec")]
+mod halfvec_ref;
+
+#[cfg(feature = "halfvec")]
+mod fixed_halfvec;
+
 #[cfg(feature = "halfvec")]
 pub use halfvec::HalfVector;
 
+#[cfg(feature = "halfvec")]
+pub use halfvec_ref::HalfVectorRef;
+
+#[cfg(feature = "halfvec")]
+pub use fixed_halfvec::FixedHalfVector;
+
+#[cfg(feature = "serde")]
+pub mod serde;
+
+pub mod vecs;
+
+#[cfg(feature = "nalgebra")]
+mod nalgebra_ext;
+
+#[cfg(feature = "arrow")]
+mod arrow_ext;
+
+#[cfg(feature = "arrow")]
+pub mod arrow {
+    pub use super::arrow_ext::bit::{
+        from_bit_array, from_bit_array_with_len, iter_bit_array, iter_bit_array_with_len,
+        to_bit_array,
+    };
+    pub use super::arrow_ext::sparsevec::{from_sparse_array, iter_sparse_array, to_sparse_array};
+    pub use super::arrow_ext::vector::{
+        from_array, refs_from_array, to_fixed_size_list_array, to_list_array,
+    };
+
+    #[cfg(feature = "halfvec")]
+    pub use super::arrow_ext::halfvec::{
+        from_half_array, half_refs_from_array, to_half_fixed_size_list_array, to_half_list_array,
+    };
+}
+
+#[cfg(feature = "candle")]
+mod candle_ext;
+
+#[cfg(feature = "candle")]
+pub mod candle {
+    pub use super::candle_ext::vector::{from_tensor, to_tensor};
+
+    #[cfg(feature = "halfvec")]
+    pub use super::candle_ext::halfvec::{from_half_tensor, to_half_tensor};
+}
+
+#[cfg(feature = "ndarray")]
+mod ndarray_ext;
+
+#[cfg(feature = "ndarray")]
+pub mod ndarray {
+    pub use super::ndarray_ext::vector::to_array2;
+
+    #[cfg(feature = "halfvec")]
+    pub use super::ndarray_ext::halfvec::to_half_array2;
+}
+
+#[cfg(feature = "npy")]
+mod npy_ext;
+
+#[cfg(feature = "npy")]
+pub mod npy {
+    pub use super::npy_ext::{from_npz, open, read, save, write, NpyRows, NpyVector};
+    pub use npyz::npz::NpzArchive;
+}
+
 #[cfg(feature = "postgres")]
 mod postgres_ext;
 
+#[cfg(any(feature = "postgres-client", feature = "tokio-postgres"))]
+pub mod postgres {
+    pub use super::postgres_ext::types::Types;
+
+    #[cfg(feature = "postgres-client")]
+    pub use super::postgres_ext::types::types;
+
+    #[cfg(feature = "tokio-postgres")]
+    pub use super::postgres_ext::types::types_async;
+}
+
 #[cfg(feature = "sqlx")]
 mod sqlx_ext;
 
@@ -26,6 +120,7 @@ mod diesel_ext;
 #[cfg(feature = "diesel")]
 pub mod sql_types {
     pub use super::diesel_ext::bit::BitType as Bit;
+    pub use super::diesel_ext::bit::VarBitType as VarBit;
     pub use super::diesel_ext::sparsevec::SparseVectorType as SparseVector;
     pub use super::diesel_ext::vector::VectorType as Vector;
 
@@ -0,0 +1,43 @@
+use crate::Error;
+
+// checks the number of dimensions against the limits of the server
+pub(crate) fn check_dims(dim: usize, type_name: &'static str, max_dim: usize) -> Result<(), Error> {
+    if dim < 1 {
+        return Err(Error::TooFewDimensions { type_name });
+    }
+    if dim > max_dim {
+        return Err(Error::TooManyDimensions {
+            type_name,
+            max: max_dim,
+        });
+    }
+    Ok(())
+}
+
+// returns the length shared by all rows, or None if there are no rows
+#[cfg(any(
+    feature = "ndarray",
+    feature = "candle",
+    feature = "arrow",
+    feature = "npy"
+))]
+pub(crate) fn same_len(
+    lens: impl IntoIterator<Item = usize>,
+    type_name: &'static str,
+) -> Result<Option<usize>, Error> {
+    let mut lens = lens.into_iter();
+    let first = match lens.next() {
+        Some(v) => v,
+        None => return Ok(None),
+    };
+    for len in lens {
+        if len != first {
+            return Err(Error::DifferentDimensions {
+                type_name,
+                left: first,
+                right: len,
+            });
+        }
+    }
+    Ok(Some(first))
+}
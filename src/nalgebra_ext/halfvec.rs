@@ -0,0 +1,128 @@
+use half::f16;
+use nalgebra::{DVector, SVector};
+
+use crate::halfvec::to_half;
+use crate::{Error, FixedHalfVector, HalfVector};
+
+impl From<DVector<f16>> for HalfVector {
+    fn from(v: DVector<f16>) -> Self {
+        HalfVector::from(Vec::from(v.data))
+    }
+}
+
+impl From<&DVector<f16>> for HalfVector {
+    fn from(v: &DVector<f16>) -> Self {
+        HalfVector::from(v.as_slice().to_vec())
+    }
+}
+
+impl<const N: usize> From<SVector<f16, N>> for HalfVector {
+    fn from(v: SVector<f16, N>) -> Self {
+        HalfVector::from(v.as_slice().to_vec())
+    }
+}
+
+impl TryFrom<&DVector<f32>> for HalfVector {
+    type Error = Error;
+
+    fn try_from(v: &DVector<f32>) -> Result<Self, Self::Error> {
+        v.iter()
+            .map(|v| to_half(*v))
+            .collect::<Result<_, _>>()
+            .map(HalfVector)
+    }
+}
+
+impl TryFrom<DVector<f32>> for HalfVector {
+    type Error = Error;
+
+    fn try_from(v: DVector<f32>) -> Result<Self, Self::Error> {
+        HalfVector::try_from(&v)
+    }
+}
+
+impl From<HalfVector> for DVector<f16> {
+    fn from(v: HalfVector) -> Self {
+        DVector::from_vec(v.0)
+    }
+}
+
+impl From<&HalfVector> for DVector<f16> {
+    fn from(v: &HalfVector) -> Self {
+        DVector::from_column_slice(v.as_slice())
+    }
+}
+
+impl<const N: usize> TryFrom<&HalfVector> for SVector<f16, N> {
+    type Error = Error;
+
+    fn try_from(v: &HalfVector) -> Result<Self, Self::Error> {
+        FixedHalfVector::<N>::try_from(v).map(SVector::from)
+    }
+}
+
+impl<const N: usize> TryFrom<HalfVector> for SVector<f16, N> {
+    type Error = Error;
+
+    fn try_from(v: HalfVector) -> Result<Self, Self::Error> {
+        SVector::try_from(&v)
+    }
+}
+
+impl<const N: usize> From<SVector<f16, N>> for FixedHalfVector<N> {
+    fn from(v: SVector<f16, N>) -> Self {
+        FixedHalfVector(v.into())
+    }
+}
+
+impl<const N: usize> From<FixedHalfVector<N>> for SVector<f16, N> {
+    fn from(v: FixedHalfVector<N>) -> Self {
+        SVector::from(v.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, FixedHalfVector, HalfVector};
+    use half::f16;
+    use nalgebra::{DVector, SVector};
+
+    #[test]
+    fn test_dvector() {
+        let vec = HalfVector::from_f32_slice(&[1.0, 2.0, 3.0]);
+        let dvec = DVector::from_vec(vec.to_vec());
+        assert_eq!(vec, HalfVector::from(&dvec));
+        assert_eq!(dvec, DVector::from(&vec));
+        assert_eq!(vec, HalfVector::from(dvec.clone()));
+        assert_eq!(dvec, DVector::from(vec.clone()));
+
+        let dvec = DVector::from_vec(vec![1.0, 2.0, 3.0]);
+        assert_eq!(vec, HalfVector::try_from(dvec).unwrap());
+        assert_eq!(
+            Err(Error::OutOfRange {
+                type_name: "halfvec",
+                value: "65520".to_string()
+            }),
+            HalfVector::try_from(DVector::from_vec(vec![65520.0]))
+        );
+    }
+
+    #[test]
+    fn test_svector() {
+        let vec = HalfVector::from_f32_slice(&[1.0, 2.0]);
+        let svec = SVector::<f16, 2>::new(f16::from_f32(1.0), f16::from_f32(2.0));
+        assert_eq!(vec, HalfVector::from(svec));
+        assert_eq!(svec, SVector::<f16, 2>::try_from(&vec).unwrap());
+        assert_eq!(
+            Err(Error::UnexpectedDimensions {
+                expected: 3,
+                actual: 2
+            }),
+            SVector::<f16, 3>::try_from(vec)
+        );
+
+        let fixed_vec = FixedHalfVector::from([f16::from_f32(1.0), f16::from_f32(2.0)]);
+        assert_eq!(fixed_vec, FixedHalfVector::from(svec));
+        assert_eq!(svec, SVector::from(fixed_vec));
+    }
+}
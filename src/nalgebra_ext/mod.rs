@@ -0,0 +1,4 @@
+mod vector;
+
+#[cfg(feature = "halfvec")]
+mod halfvec;
@@ -0,0 +1,119 @@
+use nalgebra::{DVector, SVector};
+
+use crate::{Error, FixedVector, Vector, VectorRef};
+
+impl From<DVector<f32>> for Vector {
+    fn from(v: DVector<f32>) -> Self {
+        Vector::from(Vec::from(v.data))
+    }
+}
+
+impl From<&DVector<f32>> for Vector {
+    fn from(v: &DVector<f32>) -> Self {
+        Vector::from(v.as_slice().to_vec())
+    }
+}
+
+impl<const N: usize> From<SVector<f32, N>> for Vector {
+    fn from(v: SVector<f32, N>) -> Self {
+        Vector::from(v.as_slice().to_vec())
+    }
+}
+
+impl From<Vector> for DVector<f32> {
+    fn from(v: Vector) -> Self {
+        DVector::from_vec(v.0)
+    }
+}
+
+impl From<&Vector> for DVector<f32> {
+    fn from(v: &Vector) -> Self {
+        DVector::from_column_slice(v.as_slice())
+    }
+}
+
+impl<const N: usize> TryFrom<&Vector> for SVector<f32, N> {
+    type Error = Error;
+
+    fn try_from(v: &Vector) -> Result<Self, Self::Error> {
+        FixedVector::<N>::try_from(v).map(SVector::from)
+    }
+}
+
+impl<const N: usize> TryFrom<Vector> for SVector<f32, N> {
+    type Error = Error;
+
+    fn try_from(v: Vector) -> Result<Self, Self::Error> {
+        SVector::try_from(&v)
+    }
+}
+
+impl<const N: usize> From<SVector<f32, N>> for FixedVector<N> {
+    fn from(v: SVector<f32, N>) -> Self {
+        FixedVector(v.into())
+    }
+}
+
+impl<const N: usize> From<FixedVector<N>> for SVector<f32, N> {
+    fn from(v: FixedVector<N>) -> Self {
+        SVector::from(v.0)
+    }
+}
+
+impl<'a> From<&'a DVector<f32>> for VectorRef<'a> {
+    fn from(v: &'a DVector<f32>) -> Self {
+        VectorRef::from(v.as_slice())
+    }
+}
+
+impl<'a, const N: usize> From<&'a SVector<f32, N>> for VectorRef<'a> {
+    fn from(v: &'a SVector<f32, N>) -> Self {
+        VectorRef::from(v.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, FixedVector, Vector, VectorRef};
+    use nalgebra::{DVector, SVector, Vector3};
+
+    #[test]
+    fn test_dvector() {
+        let vec = Vector::from(vec![1.0, 2.0, 3.0]);
+        let dvec = DVector::from_vec(vec![1.0, 2.0, 3.0]);
+        assert_eq!(vec, Vector::from(&dvec));
+        assert_eq!(dvec, DVector::from(&vec));
+        assert_eq!(vec, Vector::from(dvec.clone()));
+        assert_eq!(dvec, DVector::from(vec));
+    }
+
+    #[test]
+    fn test_svector() {
+        let vec = Vector::from(vec![1.0, 2.0, 3.0]);
+        let svec = Vector3::new(1.0, 2.0, 3.0);
+        assert_eq!(vec, Vector::from(svec));
+        assert_eq!(svec, SVector::<f32, 3>::try_from(&vec).unwrap());
+        assert_eq!(
+            Err(Error::UnexpectedDimensions {
+                expected: 4,
+                actual: 3
+            }),
+            SVector::<f32, 4>::try_from(vec)
+        );
+
+        let fixed_vec = FixedVector::from([1.0, 2.0, 3.0]);
+        assert_eq!(fixed_vec, FixedVector::from(svec));
+        assert_eq!(svec, SVector::from(fixed_vec));
+    }
+
+    #[test]
+    fn test_vector_ref() {
+        let dvec = DVector::from_vec(vec![1.0, 2.0, 3.0]);
+        let svec = Vector3::new(1.0, 2.0, 3.0);
+        assert_eq!(VectorRef::from(&dvec), VectorRef::from(&svec));
+        assert_eq!(
+            vec![1.0, 2.0, 3.0],
+            VectorRef::from(&dvec).iter().collect::<Vec<f32>>()
+        );
+    }
+}
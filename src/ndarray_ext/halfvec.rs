@@ -0,0 +1,101 @@
+use half::f16;
+use ndarray::{Array1, Array2, ArrayView1};
+
+use crate::halfvec::to_half;
+use crate::{Error, HalfVector};
+
+impl From<ArrayView1<'_, f16>> for HalfVector {
+    fn from(v: ArrayView1<'_, f16>) -> Self {
+        HalfVector::from(v.to_vec())
+    }
+}
+
+impl From<Array1<f16>> for HalfVector {
+    fn from(v: Array1<f16>) -> Self {
+        HalfVector::from(v.to_vec())
+    }
+}
+
+impl TryFrom<ArrayView1<'_, f32>> for HalfVector {
+    type Error = Error;
+
+    fn try_from(v: ArrayView1<'_, f32>) -> Result<Self, Self::Error> {
+        v.iter()
+            .map(|v| to_half(*v))
+            .collect::<Result<_, _>>()
+            .map(HalfVector)
+    }
+}
+
+impl TryFrom<Array1<f32>> for HalfVector {
+    type Error = Error;
+
+    fn try_from(v: Array1<f32>) -> Result<Self, Self::Error> {
+        HalfVector::try_from(v.view())
+    }
+}
+
+impl From<HalfVector> for Array1<f16> {
+    fn from(v: HalfVector) -> Self {
+        Array1::from(v.0)
+    }
+}
+
+impl<'a> From<&'a HalfVector> for ArrayView1<'a, f16> {
+    fn from(v: &'a HalfVector) -> Self {
+        ArrayView1::from(v.as_slice())
+    }
+}
+
+/// Stacks half vectors into a matrix with one row per half vector.
+pub fn to_half_array2(vecs: &[HalfVector]) -> Result<Array2<f16>, Error> {
+    super::stack(vecs.iter().map(|v| v.as_slice()), "halfvec")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, HalfVector};
+    use half::f16;
+    use ndarray::{array, Array1, ArrayView1};
+
+    #[test]
+    fn test_from_array() {
+        let expected = HalfVector::from_f32_slice(&[1.0, 2.0, 3.0]);
+        let arr = array![1.0, 2.0, 3.0].map(|v| f16::from_f32(*v));
+        assert_eq!(expected, HalfVector::from(arr.view()));
+        assert_eq!(expected, HalfVector::from(arr));
+
+        let arr = array![1.0, 2.0, 3.0];
+        assert_eq!(expected, HalfVector::try_from(arr.view()).unwrap());
+        assert_eq!(expected, HalfVector::try_from(arr).unwrap());
+
+        assert_eq!(
+            Err(Error::OutOfRange {
+                type_name: "halfvec",
+                value: "65520".to_string()
+            }),
+            HalfVector::try_from(array![65520.0])
+        );
+    }
+
+    #[test]
+    fn test_to_array() {
+        let vec = HalfVector::from_f32_slice(&[1.0, 2.0, 3.0]);
+        let expected = array![1.0, 2.0, 3.0].map(|v| f16::from_f32(*v));
+        assert_eq!(expected, ArrayView1::from(&vec));
+        assert_eq!(expected, Array1::from(vec));
+    }
+
+    #[test]
+    fn test_to_half_array2() {
+        let vecs = vec![
+            HalfVector::from_f32_slice(&[1.0, 2.0]),
+            HalfVector::from_f32_slice(&[3.0, 4.0]),
+        ];
+        let arr = crate::ndarray::to_half_array2(&vecs).unwrap();
+        assert_eq!(
+            array![[1.0, 2.0], [3.0, 4.0]].map(|v| f16::from_f32(*v)),
+            arr
+        );
+    }
+}
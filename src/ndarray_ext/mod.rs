@@ -0,0 +1,20 @@
+pub(crate) mod vector;
+
+#[cfg(feature = "halfvec")]
+pub(crate) mod halfvec;
+
+use ndarray::Array2;
+
+use crate::{limits, Error};
+
+// stacks rows of the same length into a matrix
+fn stack<'a, T: Copy + 'a>(
+    rows: impl ExactSizeIterator<Item = &'a [T]> + Clone,
+    type_name: &'static str,
+) -> Result<Array2<T>, Error> {
+    let nrows = rows.len();
+    let ncols = limits::same_len(rows.clone().map(|v| v.len()), type_name)?.unwrap_or(0);
+    let data = rows.flat_map(|v| v.iter().copied()).collect();
+    // cannot fail since every row has ncols values
+    Ok(Array2::from_shape_vec((nrows, ncols), data).unwrap())
+}
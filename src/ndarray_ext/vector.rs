@@ -0,0 +1,103 @@
+use ndarray::{Array1, Array2, ArrayView1};
+
+use crate::{Error, Vector, VectorRef};
+
+impl From<ArrayView1<'_, f32>> for Vector {
+    fn from(v: ArrayView1<'_, f32>) -> Self {
+        Vector::from(v.to_vec())
+    }
+}
+
+impl From<Array1<f32>> for Vector {
+    fn from(v: Array1<f32>) -> Self {
+        Vector::from(v.to_vec())
+    }
+}
+
+impl From<Vector> for Array1<f32> {
+    fn from(v: Vector) -> Self {
+        Array1::from(v.0)
+    }
+}
+
+impl<'a> From<&'a Vector> for ArrayView1<'a, f32> {
+    fn from(v: &'a Vector) -> Self {
+        ArrayView1::from(v.as_slice())
+    }
+}
+
+impl<'a> From<ArrayView1<'a, f32>> for VectorRef<'a> {
+    fn from(v: ArrayView1<'a, f32>) -> Self {
+        match v.to_slice() {
+            Some(v) => VectorRef::from(v),
+            None => VectorRef::from_view(v),
+        }
+    }
+}
+
+/// Stacks vectors into a matrix with one row per vector.
+pub fn to_array2(vecs: &[Vector]) -> Result<Array2<f32>, Error> {
+    super::stack(vecs.iter().map(|v| v.as_slice()), "vector")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Vector, VectorRef};
+    use ndarray::{array, s, Array1, Array2, ArrayView1};
+
+    #[test]
+    fn test_from_array() {
+        let arr = array![1.0, 2.0, 3.0];
+        assert_eq!(Vector::from(vec![1.0, 2.0, 3.0]), Vector::from(arr.view()));
+        assert_eq!(Vector::from(vec![1.0, 2.0, 3.0]), Vector::from(arr));
+
+        let arr = array![[1.0, 2.0], [3.0, 4.0]];
+        assert_eq!(Vector::from(vec![1.0, 3.0]), Vector::from(arr.column(0)));
+    }
+
+    #[test]
+    fn test_to_array() {
+        let vec = Vector::from(vec![1.0, 2.0, 3.0]);
+        assert_eq!(array![1.0, 2.0, 3.0], ArrayView1::from(&vec));
+        assert_eq!(array![1.0, 2.0, 3.0], Array1::from(vec));
+    }
+
+    #[test]
+    fn test_vector_ref() {
+        let arr = array![1.0, 2.0, 3.0, 4.0];
+        let vec = VectorRef::from(arr.view());
+        assert_eq!(vec![1.0, 2.0, 3.0, 4.0], vec.iter().collect::<Vec<f32>>());
+
+        let vec = VectorRef::from(arr.slice(s![..;2]));
+        assert_eq!(2, vec.len());
+        assert_eq!(vec![1.0, 3.0], vec.iter().collect::<Vec<f32>>());
+        let mut dst = [0.0; 2];
+        vec.copy_to_slice(&mut dst);
+        assert_eq!([1.0, 3.0], dst);
+        assert_eq!(Vector::from(vec![1.0, 3.0]), vec.to_vector());
+    }
+
+    #[test]
+    fn test_to_array2() {
+        let vecs = vec![
+            Vector::from(vec![1.0, 2.0]),
+            Vector::from(vec![3.0, 4.0]),
+            Vector::from(vec![5.0, 6.0]),
+        ];
+        let arr = crate::ndarray::to_array2(&vecs).unwrap();
+        assert_eq!(array![[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]], arr);
+
+        let arr = crate::ndarray::to_array2(&[]).unwrap();
+        assert_eq!(Array2::<f32>::zeros((0, 0)), arr);
+
+        let vecs = vec![Vector::from(vec![1.0, 2.0]), Vector::from(vec![3.0])];
+        assert_eq!(
+            Err(Error::DifferentDimensions {
+                type_name: "vector",
+                left: 2,
+                right: 1
+            }),
+            crate::ndarray::to_array2(&vecs)
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use half::f16;
+
+use super::NpyVector;
+use crate::halfvec::{to_half, HALFVEC_MAX_DIM};
+use crate::text;
+use crate::{Error, HalfVector};
+
+impl NpyVector for HalfVector {
+    type Elem = f16;
+
+    const TYPE_NAME: &'static str = "halfvec";
+
+    const MAX_DIM: usize = HALFVEC_MAX_DIM;
+
+    fn from_row(row: Vec<f32>) -> Result<Self, Error> {
+        let vec = row
+            .into_iter()
+            .map(to_half)
+            .collect::<Result<Vec<f16>, _>>()?;
+        for v in &vec {
+            text::check_element(v.to_f32(), "halfvec")?;
+        }
+        Ok(HalfVector::from(vec))
+    }
+
+    fn elems(&self) -> &[f16] {
+        self.as_slice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::npy::{read, write};
+    use crate::{HalfVector, Vector};
+    use std::io::{self, Cursor};
+
+    #[test]
+    fn test_half_vector() {
+        let vecs = vec![
+            HalfVector::from_f32_slice(&[1.0, 2.0]),
+            HalfVector::from_f32_slice(&[3.0, 4.0]),
+        ];
+        let mut buf = Vec::new();
+        write(&mut buf, &vecs).unwrap();
+
+        let rows = read::<HalfVector, _>(Cursor::new(buf.clone())).unwrap();
+        assert_eq!(vecs, rows.collect::<io::Result<Vec<_>>>().unwrap());
+
+        // float16 arrays can also be read as vectors
+        let rows = read::<Vector, _>(Cursor::new(buf)).unwrap();
+        assert_eq!(
+            vec![Vector::from(vec![1.0, 2.0]), Vector::from(vec![3.0, 4.0])],
+            rows.collect::<io::Result<Vec<_>>>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_from_float32() {
+        let mut buf = Vec::new();
+        write(&mut buf, &[Vector::from(vec![1.0, 65520.0])]).unwrap();
+        let mut rows = read::<HalfVector, _>(Cursor::new(buf)).unwrap();
+        assert!(rows.next().unwrap().is_err());
+    }
+}
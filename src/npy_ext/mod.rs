@@ -0,0 +1,231 @@
+pub(crate) mod vector;
+
+#[cfg(feature = "halfvec")]
+pub(crate) mod halfvec;
+
+use npyz::npz::NpzArchive;
+use npyz::{AutoSerialize, DType, NpyFile, Order, WriteOptions, WriterBuilder};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Seek, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+
+use crate::limits;
+use crate::Error;
+
+#[cfg(feature = "halfvec")]
+use half::f16;
+
+/// A vector type that can be read from and written to `.npy` files.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait NpyVector: private::Sealed + Sized {
+    #[doc(hidden)]
+    type Elem: AutoSerialize + Copy;
+
+    #[doc(hidden)]
+    const TYPE_NAME: &'static str;
+
+    #[doc(hidden)]
+    const MAX_DIM: usize;
+
+    #[doc(hidden)]
+    fn from_row(row: Vec<f32>) -> Result<Self, Error>;
+
+    #[doc(hidden)]
+    fn elems(&self) -> &[Self::Elem];
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for crate::Vector {}
+
+    #[cfg(feature = "halfvec")]
+    impl Sealed for crate::HalfVector {}
+}
+
+enum Values<R: Read> {
+    Float32(npyz::NpyReader<f32, R>),
+    #[cfg(feature = "halfvec")]
+    Float16(npyz::NpyReader<f16, R>),
+    // column-major values
+    Fortran(Vec<f32>),
+}
+
+impl<R: Read> Values<R> {
+    fn new(file: NpyFile<R>) -> Result<Values<R>, NpyFile<R>> {
+        let file = match file.try_data::<f32>() {
+            Ok(r) => return Ok(Values::Float32(r)),
+            Err(file) => file,
+        };
+        #[cfg(feature = "halfvec")]
+        let file = match file.try_data::<f16>() {
+            Ok(r) => return Ok(Values::Float16(r)),
+            Err(file) => file,
+        };
+        Err(file)
+    }
+
+    fn next_value(&mut self) -> io::Result<f32> {
+        let value = match self {
+            Values::Float32(r) => r.next(),
+            #[cfg(feature = "halfvec")]
+            Values::Float16(r) => r.next().map(|v| v.map(f32::from)),
+            Values::Fortran(_) => unreachable!(),
+        };
+        value.unwrap_or_else(|| Err(io::ErrorKind::UnexpectedEof.into()))
+    }
+}
+
+/// An iterator over the rows of a 2-D array.
+pub struct NpyRows<R: Read, T> {
+    values: Values<R>,
+    nrows: usize,
+    dim: usize,
+    row: usize,
+    marker: PhantomData<T>,
+}
+
+impl<R: Read, T: NpyVector> NpyRows<R, T> {
+    fn new(file: NpyFile<R>) -> io::Result<NpyRows<R, T>> {
+        let shape = file.shape().to_vec();
+        if shape.len() != 2 {
+            return Err(invalid_data(Error::InvalidShape { ndim: shape.len() }));
+        }
+        let nrows = usize::try_from(shape[0]).unwrap_or(usize::MAX);
+        let dim = usize::try_from(shape[1]).unwrap_or(usize::MAX);
+        // write stores no vectors as a (0, 0) array
+        if nrows > 0 {
+            limits::check_dims(dim, T::TYPE_NAME, T::MAX_DIM).map_err(invalid_data)?;
+        }
+
+        let order = file.order();
+        let mut values = Values::new(file).map_err(|file| {
+            invalid_data(Error::UnsupportedDataType {
+                type_name: T::TYPE_NAME,
+                data_type: match file.dtype() {
+                    DType::Plain(ty) => ty.to_string(),
+                    dtype => dtype.descr(),
+                },
+            })
+        })?;
+
+        // rows are not contiguous, so read the entire array
+        if order == Order::Fortran && nrows > 1 {
+            let len = nrows.checked_mul(dim).ok_or_else(|| {
+                invalid_data(Error::DimensionOverflow {
+                    type_name: T::TYPE_NAME,
+                    value: i64::try_from(nrows).unwrap_or(i64::MAX),
+                })
+            })?;
+            // grow as values are read rather than trusting the header
+            let mut data = Vec::new();
+            for _ in 0..len {
+                data.push(values.next_value()?);
+            }
+            values = Values::Fortran(data);
+        }
+
+        Ok(NpyRows {
+            values,
+            nrows,
+            dim,
+            row: 0,
+            marker: PhantomData,
+        })
+    }
+
+    fn read_row(&mut self) -> io::Result<T> {
+        let row = match &self.values {
+            Values::Fortran(data) => (0..self.dim)
+                .map(|j| data[j * self.nrows + self.row])
+                .collect(),
+            _ => (0..self.dim)
+                .map(|_| self.values.next_value())
+                .collect::<io::Result<Vec<f32>>>()?,
+        };
+        T::from_row(row).map_err(invalid_data)
+    }
+}
+
+impl<R: Read, T: NpyVector> Iterator for NpyRows<R, T> {
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.row >= self.nrows {
+            return None;
+        }
+        let row = self.read_row();
+        // stop after an error since the position is unknown
+        self.row = if row.is_ok() {
+            self.row + 1
+        } else {
+            self.nrows
+        };
+        Some(row)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.nrows - self.row;
+        (n, Some(n))
+    }
+}
+
+impl<R: Read, T: NpyVector> ExactSizeIterator for NpyRows<R, T> {}
+
+/// Reads the rows of a 2-D `float32` or `float16` array from a `.npy` file.
+///
+/// Arrays in Fortran order are read into memory.
+pub fn read<T: NpyVector, R: Read>(reader: R) -> io::Result<NpyRows<R, T>> {
+    NpyRows::new(NpyFile::new(reader)?)
+}
+
+/// Opens a `.npy` file with a buffered reader.
+pub fn open<T: NpyVector, P: AsRef<Path>>(path: P) -> io::Result<NpyRows<BufReader<File>, T>> {
+    read(BufReader::new(File::open(path)?))
+}
+
+/// Reads the rows of an array in a `.npz` archive.
+pub fn from_npz<'a, T: NpyVector, R: Read + Seek>(
+    archive: &'a mut NpzArchive<R>,
+    name: &str,
+) -> io::Result<NpyRows<impl Read + 'a, T>> {
+    match archive.by_name(name)? {
+        Some(file) => NpyRows::new(file),
+        None => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("array {} not found", name),
+        )),
+    }
+}
+
+/// Writes vectors with the same number of dimensions as a 2-D array.
+pub fn write<T: NpyVector, W: Write>(writer: W, vecs: &[T]) -> io::Result<()> {
+    let dim = limits::same_len(vecs.iter().map(|v| v.elems().len()), T::TYPE_NAME)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+        .unwrap_or(0);
+
+    let mut writer = WriteOptions::<T::Elem>::new()
+        .default_dtype()
+        .shape(&[vecs.len() as u64, dim as u64])
+        .writer(writer)
+        .begin_nd()?;
+    for v in vecs {
+        for e in v.elems() {
+            writer.push(e)?;
+        }
+    }
+    writer.finish()
+}
+
+/// Saves vectors with the same number of dimensions to a `.npy` file.
+pub fn save<T: NpyVector, P: AsRef<Path>>(path: P, vecs: &[T]) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write(&mut writer, vecs)?;
+    writer.flush()
+}
+
+fn invalid_data(e: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
@@ -0,0 +1,187 @@
+use super::NpyVector;
+use crate::vector::VECTOR_MAX_DIM;
+use crate::{Error, Vector};
+
+impl NpyVector for Vector {
+    type Elem = f32;
+
+    const TYPE_NAME: &'static str = "vector";
+
+    const MAX_DIM: usize = VECTOR_MAX_DIM;
+
+    fn from_row(row: Vec<f32>) -> Result<Self, Error> {
+        Vector::try_new(row)
+    }
+
+    fn elems(&self) -> &[f32] {
+        self.as_slice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::npy::{from_npz, open, read, save, write, NpzArchive};
+    use crate::{Error, Vector};
+    use npyz::npz::NpzWriter;
+    use npyz::{Order, WriteOptions, WriterBuilder};
+    use std::io::{self, Cursor};
+
+    fn npy<T: npyz::AutoSerialize + Copy>(shape: &[u64], order: Order, data: &[T]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        let mut writer = WriteOptions::new()
+            .default_dtype()
+            .shape(shape)
+            .order(order)
+            .writer(&mut buf)
+            .begin_nd()
+            .unwrap();
+        writer.extend(data.iter().copied()).unwrap();
+        writer.finish().unwrap();
+        buf
+    }
+
+    #[test]
+    fn test_write() {
+        let vecs = vec![
+            Vector::from(vec![1.0, 2.0, 3.0]),
+            Vector::from(vec![4.0, 5.0, 6.0]),
+        ];
+        let mut buf = Vec::new();
+        write(&mut buf, &vecs).unwrap();
+        let rows = read::<Vector, _>(Cursor::new(buf)).unwrap();
+        assert_eq!(2, rows.len());
+        assert_eq!(vecs, rows.collect::<io::Result<Vec<_>>>().unwrap());
+    }
+
+    #[test]
+    fn test_write_empty() {
+        let mut buf = Vec::new();
+        write(&mut buf, &[] as &[Vector]).unwrap();
+        let rows = read::<Vector, _>(Cursor::new(buf)).unwrap();
+        assert_eq!(0, rows.len());
+        assert_eq!(0, rows.count());
+    }
+
+    #[test]
+    fn test_fortran_order() {
+        let vecs = vec![
+            Vector::from(vec![1.0, 2.0, 3.0]),
+            Vector::from(vec![4.0, 5.0, 6.0]),
+        ];
+        let buf = npy(&[2, 3], Order::Fortran, &[1.0f32, 4.0, 2.0, 5.0, 3.0, 6.0]);
+        let rows = read::<Vector, _>(Cursor::new(buf)).unwrap();
+        assert_eq!(vecs, rows.collect::<io::Result<Vec<_>>>().unwrap());
+    }
+
+    #[test]
+    fn test_fortran_order_oversized() {
+        // shape is (2^58, 16) but only a few values follow
+        let header = "{'descr': '<f4', 'fortran_order': True, 'shape': (288230376151711744, 16), }";
+        let mut buf = b"\x93NUMPY\x01\x00".to_vec();
+        buf.extend_from_slice(&(header.len() as u16 + 1).to_le_bytes());
+        buf.extend_from_slice(header.as_bytes());
+        buf.push(b'\n');
+        buf.extend_from_slice(&[0; 16]);
+        let err = read::<Vector, _>(Cursor::new(buf)).err().unwrap();
+        assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+    }
+
+    #[test]
+    fn test_file() {
+        let vecs = vec![
+            Vector::from(vec![1.0, 2.0, 3.0]),
+            Vector::from(vec![4.0, 5.0, 6.0]),
+        ];
+        let path = std::env::temp_dir().join(format!("pgvector-{}.npy", std::process::id()));
+        save(&path, &vecs).unwrap();
+        let rows = open::<Vector, _>(&path).unwrap();
+        assert_eq!(vecs, rows.collect::<io::Result<Vec<_>>>().unwrap());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_npz() {
+        let vecs = vec![
+            Vector::from(vec![1.0, 2.0, 3.0]),
+            Vector::from(vec![4.0, 5.0, 6.0]),
+        ];
+        let mut buf = Cursor::new(Vec::new());
+        let mut writer = NpzWriter::new(&mut buf);
+        let mut array = writer
+            .array::<f32>("embeddings", Default::default())
+            .unwrap()
+            .default_dtype()
+            .shape(&[2, 3])
+            .begin_nd()
+            .unwrap();
+        array.extend([1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+        array.finish().unwrap();
+        drop(writer);
+
+        let mut archive = NpzArchive::new(Cursor::new(buf.into_inner())).unwrap();
+        let rows = from_npz::<Vector, _>(&mut archive, "embeddings").unwrap();
+        assert_eq!(vecs, rows.collect::<io::Result<Vec<_>>>().unwrap());
+
+        let err = from_npz::<Vector, _>(&mut archive, "missing")
+            .err()
+            .unwrap();
+        assert_eq!(io::ErrorKind::NotFound, err.kind());
+    }
+
+    #[test]
+    fn test_invalid() {
+        let buf = npy(&[3], Order::C, &[1.0f32, 2.0, 3.0]);
+        let err = read::<Vector, _>(Cursor::new(buf)).err().unwrap();
+        assert_eq!(
+            Some(&Error::InvalidShape { ndim: 1 }),
+            err.get_ref().and_then(|e| e.downcast_ref::<Error>())
+        );
+
+        let buf = npy(&[1, 3], Order::C, &[1.0f64, 2.0, 3.0]);
+        let err = read::<Vector, _>(Cursor::new(buf)).err().unwrap();
+        assert_eq!(
+            Some(&Error::UnsupportedDataType {
+                type_name: "vector",
+                data_type: "<f8".to_string()
+            }),
+            err.get_ref().and_then(|e| e.downcast_ref::<Error>())
+        );
+
+        let buf = npy(&[1, 0], Order::C, &[0.0f32; 0]);
+        let err = read::<Vector, _>(Cursor::new(buf)).err().unwrap();
+        assert_eq!(
+            Some(&Error::TooFewDimensions {
+                type_name: "vector"
+            }),
+            err.get_ref().and_then(|e| e.downcast_ref::<Error>())
+        );
+
+        let buf = npy(&[1, 2], Order::C, &[1.0f32, f32::NAN]);
+        let mut rows = read::<Vector, _>(Cursor::new(buf)).unwrap();
+        assert_eq!(
+            Some(&Error::NaN {
+                type_name: "vector"
+            }),
+            rows.next()
+                .unwrap()
+                .unwrap_err()
+                .get_ref()
+                .and_then(|e| e.downcast_ref::<Error>())
+        );
+        assert!(rows.next().is_none());
+    }
+
+    #[test]
+    fn test_write_different_dimensions() {
+        let vecs = vec![Vector::from(vec![1.0, 2.0]), Vector::from(vec![3.0])];
+        let err = write(Vec::new(), &vecs).unwrap_err();
+        assert_eq!(
+            Some(&Error::DifferentDimensions {
+                type_name: "vector",
+                left: 2,
+                right: 1
+            }),
+            err.get_ref().and_then(|e| e.downcast_ref::<Error>())
+        );
+    }
+}
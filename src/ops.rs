@@ -0,0 +1,134 @@
+// Operations shared by the vector and bit string types
+//
+// The binary operators return a Result since the server errors on different
+// dimensions and overflow. The assignment operators cannot return an error,
+// so they panic instead.
+
+use std::ops::Range;
+
+use crate::Error;
+
+// returns the range for a 1-based subvector like the server
+pub(crate) fn subvector_range(
+    dim: usize,
+    start: i32,
+    count: i32,
+    type_name: &'static str,
+) -> Result<Range<usize>, Error> {
+    let error = Error::TooFewDimensions { type_name };
+    if count <= 0 {
+        return Err(error);
+    }
+
+    // cannot overflow
+    let dim = dim as i64;
+    let mut start = i64::from(start);
+    let mut end = start + i64::from(count);
+
+    // indexing starts at 1, like substring
+    if start < 1 {
+        start = 1;
+    } else if start > dim {
+        return Err(error);
+    }
+    if end > dim {
+        end = dim + 1;
+    }
+    if end <= start {
+        return Err(error);
+    }
+
+    Ok((start - 1) as usize..(end - 1) as usize)
+}
+
+// checks the result of an operation like the server
+pub(crate) fn check_result(r: f32) -> Result<f32, Error> {
+    if r.is_infinite() {
+        return Err(Error::Overflow);
+    }
+    Ok(r)
+}
+
+// checks the result of a multiplication like the server
+pub(crate) fn check_product(r: f32, a: f32, b: f32) -> Result<f32, Error> {
+    let r = check_result(r)?;
+    if r == 0.0 && !(a == 0.0 || b == 0.0) {
+        return Err(Error::Underflow);
+    }
+    Ok(r)
+}
+
+macro_rules! impl_op {
+    ($t:ty, $op:ident, $method:ident, $assign:ident, $assign_method:ident, $f:ident) => {
+        impl std::ops::$op<&$t> for &$t {
+            type Output = Result<$t, Error>;
+
+            fn $method(self, rhs: &$t) -> Self::Output {
+                self.$f(rhs)
+            }
+        }
+
+        impl std::ops::$op<&$t> for $t {
+            type Output = Result<$t, Error>;
+
+            fn $method(self, rhs: &$t) -> Self::Output {
+                self.$f(rhs)
+            }
+        }
+
+        impl std::ops::$op for $t {
+            type Output = Result<$t, Error>;
+
+            fn $method(self, rhs: $t) -> Self::Output {
+                self.$f(&rhs)
+            }
+        }
+
+        impl std::ops::$assign<&$t> for $t {
+            fn $assign_method(&mut self, rhs: &$t) {
+                *self = self.$f(rhs).unwrap_or_else(|e| panic!("{}", e));
+            }
+        }
+
+        impl std::ops::$assign for $t {
+            fn $assign_method(&mut self, rhs: $t) {
+                *self = self.$f(&rhs).unwrap_or_else(|e| panic!("{}", e));
+            }
+        }
+    };
+}
+
+// implements Add, Sub, and Mul (element-wise and scalar) and their
+// assignment counterparts on top of try_add, try_sub, try_mul, and try_scale
+macro_rules! impl_ops {
+    ($t:ty, $scalar:ty) => {
+        crate::ops::impl_op!($t, Add, add, AddAssign, add_assign, try_add);
+        crate::ops::impl_op!($t, Sub, sub, SubAssign, sub_assign, try_sub);
+        crate::ops::impl_op!($t, Mul, mul, MulAssign, mul_assign, try_mul);
+
+        impl std::ops::Mul<$scalar> for &$t {
+            type Output = Result<$t, Error>;
+
+            fn mul(self, rhs: $scalar) -> Self::Output {
+                self.try_scale(rhs)
+            }
+        }
+
+        impl std::ops::Mul<$scalar> for $t {
+            type Output = Result<$t, Error>;
+
+            fn mul(self, rhs: $scalar) -> Self::Output {
+                self.try_scale(rhs)
+            }
+        }
+
+        impl std::ops::MulAssign<$scalar> for $t {
+            fn mul_assign(&mut self, rhs: $scalar) {
+                *self = self.try_scale(rhs).unwrap_or_else(|e| panic!("{}", e));
+            }
+        }
+    };
+}
+
+pub(crate) use impl_op;
+pub(crate) use impl_ops;
@@ -7,11 +7,19 @@ use crate::Bit;
 
 impl<'a> FromSql<'a> for Bit {
     fn from_sql(_ty: &Type, raw: &'a [u8]) -> Result<Bit, Box<dyn Error + Sync + Send>> {
-        Bit::from_sql(raw)
+        let bit = Bit::from_sql(raw).or_else(|e| {
+            // fall back to the text format, which is ASCII
+            if raw.iter().all(|c| *c == b'0' || *c == b'1') {
+                std::str::from_utf8(raw).unwrap().parse()
+            } else {
+                Err(e)
+            }
+        })?;
+        Ok(bit)
     }
 
     fn accepts(ty: &Type) -> bool {
-        ty.name() == "bit"
+        matches!(ty.name(), "bit" | "varbit")
     }
 }
 
@@ -24,7 +32,7 @@ impl ToSql for Bit {
     }
 
     fn accepts(ty: &Type) -> bool {
-        ty.name() == "bit"
+        matches!(ty.name(), "bit" | "varbit")
     }
 
     to_sql_checked!();
@@ -32,9 +40,9 @@ impl ToSql for Bit {
 
 #[cfg(test)]
 mod tests {
-    use crate::Bit;
+    use crate::{Bit, Error};
     use postgres::binary_copy::BinaryCopyInWriter;
-    use postgres::types::Type;
+    use postgres::types::{FromSql, ToSql, Type};
     use postgres::{Client, NoTls};
 
     #[test]
@@ -85,6 +93,16 @@ mod tests {
         let text_res: String = text_row.get(0);
         assert_eq!("010100001", text_res);
 
+        // varbit
+        let row = client.query_one(
+            "SELECT $1::varbit, B'101'::varbit",
+            &[&Bit::new(&[true, true])],
+        )?;
+        let res_vec: Bit = row.get(0);
+        assert_eq!(Bit::new(&[true, true]), res_vec);
+        let res_vec: Bit = row.get(1);
+        assert_eq!(Bit::new(&[true, false, true]), res_vec);
+
         // copy
         let bit_type = Type::BIT;
         let writer = client
@@ -100,4 +118,43 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_accepts() {
+        assert!(<Bit as FromSql>::accepts(&Type::BIT));
+        assert!(<Bit as FromSql>::accepts(&Type::VARBIT));
+        assert!(<Bit as ToSql>::accepts(&Type::VARBIT));
+        assert!(!<Bit as FromSql>::accepts(&Type::BYTEA));
+    }
+
+    #[test]
+    fn test_from_sql_text() {
+        let vec = <Bit as FromSql>::from_sql(&Type::BIT, b"101").unwrap();
+        assert_eq!(Bit::new(&[true, false, true]), vec);
+
+        let vec = <Bit as FromSql>::from_sql(&Type::BIT, b"010100001").unwrap();
+        assert_eq!(
+            Bit::new(&[false, true, false, true, false, false, false, false, true]),
+            vec
+        );
+    }
+
+    #[test]
+    fn test_from_sql_invalid() {
+        let err = <Bit as FromSql>::from_sql(&Type::BIT, &[0, 0, 0, 9, 0]).unwrap_err();
+        assert_eq!(
+            Some(&Error::InvalidLength {
+                type_name: "bit",
+                expected: 6,
+                actual: 5
+            }),
+            err.downcast_ref::<Error>()
+        );
+    }
+
+    #[test]
+    fn test_from_sql_padding() {
+        let vec = <Bit as FromSql>::from_sql(&Type::BIT, &[0, 0, 0, 3, 0b11111111]).unwrap();
+        assert_eq!(&[0b11100000], vec.as_bytes());
+    }
 }
@@ -3,11 +3,15 @@ use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
 use std::convert::TryInto;
 use std::error::Error;
 
-use crate::HalfVector;
+use crate::{FixedHalfVector, HalfVector, HalfVectorRef};
 
 impl<'a> FromSql<'a> for HalfVector {
     fn from_sql(_ty: &Type, raw: &'a [u8]) -> Result<HalfVector, Box<dyn Error + Sync + Send>> {
-        HalfVector::from_sql(raw)
+        // the binary format cannot start with this character due to dimension limits
+        if raw.first() == Some(&b'[') {
+            return Ok(std::str::from_utf8(raw)?.parse()?);
+        }
+        Ok(HalfVector::from_sql(raw)?)
     }
 
     fn accepts(ty: &Type) -> bool {
@@ -16,12 +20,43 @@ impl<'a> FromSql<'a> for HalfVector {
 }
 
 impl ToSql for HalfVector {
+    fn to_sql(&self, ty: &Type, w: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        HalfVectorRef::from(self.as_slice()).to_sql(ty, w)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.name() == "halfvec"
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for HalfVectorRef<'a> {
+    fn from_sql(
+        _ty: &Type,
+        raw: &'a [u8],
+    ) -> Result<HalfVectorRef<'a>, Box<dyn Error + Sync + Send>> {
+        if raw.first() == Some(&b'[') {
+            return Err(crate::Error::TextFormat {
+                type_name: "halfvec",
+            }
+            .into());
+        }
+        Ok(HalfVectorRef::from_sql(raw)?)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.name() == "halfvec"
+    }
+}
+
+impl ToSql for HalfVectorRef<'_> {
     fn to_sql(&self, _ty: &Type, w: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
-        let dim = self.0.len();
+        let dim = self.len();
         w.put_u16(dim.try_into()?);
         w.put_u16(0);
 
-        for v in &self.0 {
+        for v in self.iter() {
             w.put(&v.to_be_bytes()[..]);
         }
 
@@ -35,12 +70,42 @@ impl ToSql for HalfVector {
     to_sql_checked!();
 }
 
+impl<'a, const N: usize> FromSql<'a> for FixedHalfVector<N> {
+    fn from_sql(
+        _ty: &Type,
+        raw: &'a [u8],
+    ) -> Result<FixedHalfVector<N>, Box<dyn Error + Sync + Send>> {
+        // the binary format cannot start with this character due to dimension limits
+        if raw.first() == Some(&b'[') {
+            let vec: HalfVector = std::str::from_utf8(raw)?.parse()?;
+            return Ok(vec.try_into()?);
+        }
+        Ok(FixedHalfVector::from_sql(raw)?)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.name() == "halfvec"
+    }
+}
+
+impl<const N: usize> ToSql for FixedHalfVector<N> {
+    fn to_sql(&self, ty: &Type, w: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        HalfVectorRef::from(self.as_slice()).to_sql(ty, w)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.name() == "halfvec"
+    }
+
+    to_sql_checked!();
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::HalfVector;
+    use crate::{Error, HalfVector, HalfVectorRef};
     use half::f16;
     use postgres::binary_copy::BinaryCopyInWriter;
-    use postgres::types::{Kind, Type};
+    use postgres::types::{FromSql, Kind, Type};
     use postgres::{Client, NoTls};
 
     #[test]
@@ -124,6 +189,35 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_from_sql_text() {
+        let ty = Type::new("halfvec".into(), 0, Kind::Simple, "public".into());
+        let vec = <HalfVector as FromSql>::from_sql(&ty, b"[1,2,3]").unwrap();
+        assert_eq!(HalfVector::from_f32_slice(&[1.0, 2.0, 3.0]), vec);
+
+        let err = <HalfVectorRef as FromSql>::from_sql(&ty, b"[1,2,3]").unwrap_err();
+        assert_eq!(
+            Some(&Error::TextFormat {
+                type_name: "halfvec"
+            }),
+            err.downcast_ref::<Error>()
+        );
+    }
+
+    #[test]
+    fn test_from_sql_invalid() {
+        let ty = Type::new("halfvec".into(), 0, Kind::Simple, "public".into());
+        let err = <HalfVector as FromSql>::from_sql(&ty, &[0, 2, 0, 0, 0, 0]).unwrap_err();
+        assert_eq!(
+            Some(&Error::InvalidLength {
+                type_name: "halfvec",
+                expected: 8,
+                actual: 6
+            }),
+            err.downcast_ref::<Error>()
+        );
+    }
+
     fn get_type(client: &mut Client, name: &str) -> Result<Type, postgres::Error> {
         let row = client.query_one("SELECT pg_type.oid, nspname AS schema FROM pg_type INNER JOIN pg_namespace ON pg_namespace.oid = pg_type.typnamespace WHERE typname = $1", &[&name])?;
         Ok(Type::new(
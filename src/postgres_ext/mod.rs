@@ -2,5 +2,8 @@ mod bit;
 mod sparsevec;
 mod vector;
 
+#[cfg(any(feature = "postgres-client", feature = "tokio-postgres"))]
+pub(crate) mod types;
+
 #[cfg(feature = "halfvec")]
 mod halfvec;
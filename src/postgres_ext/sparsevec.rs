@@ -7,7 +7,11 @@ use crate::SparseVector;
 
 impl<'a> FromSql<'a> for SparseVector {
     fn from_sql(_ty: &Type, raw: &'a [u8]) -> Result<SparseVector, Box<dyn Error + Sync + Send>> {
-        SparseVector::from_sql(raw)
+        // the binary format cannot start with this character due to dimension limits
+        if raw.first() == Some(&b'{') {
+            return Ok(std::str::from_utf8(raw)?.parse()?);
+        }
+        Ok(SparseVector::from_sql(raw)?)
     }
 
     fn accepts(ty: &Type) -> bool {
@@ -43,9 +47,9 @@ impl ToSql for SparseVector {
 
 #[cfg(test)]
 mod tests {
-    use crate::SparseVector;
+    use crate::{Error, SparseVector};
     use postgres::binary_copy::BinaryCopyInWriter;
-    use postgres::types::{Kind, Type};
+    use postgres::types::{FromSql, Kind, Type};
     use postgres::{Client, NoTls};
 
     #[test]
@@ -106,6 +110,67 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_from_sql_text() {
+        let ty = Type::new("sparsevec".into(), 0, Kind::Simple, "public".into());
+        let vec = <SparseVector as FromSql>::from_sql(&ty, b"{1:1,3:2}/3").unwrap();
+        assert_eq!(SparseVector::from_dense(&[1.0, 0.0, 2.0]), vec);
+    }
+
+    #[test]
+    fn test_from_sql_invalid() {
+        let ty = Type::new("sparsevec".into(), 0, Kind::Simple, "public".into());
+        let decode = |raw: &[u8]| {
+            let err = <SparseVector as FromSql>::from_sql(&ty, raw).unwrap_err();
+            err.downcast_ref::<Error>().unwrap().clone()
+        };
+
+        let header = |dim: i32, nnz: i32| [dim.to_be_bytes(), nnz.to_be_bytes(), [0; 4]].concat();
+        assert_eq!(
+            Error::DimensionOverflow {
+                type_name: "sparsevec",
+                value: -1
+            },
+            decode(&header(3, -1))
+        );
+        assert_eq!(
+            Error::InvalidLength {
+                type_name: "sparsevec",
+                expected: 20,
+                actual: 12
+            },
+            decode(&header(3, 1))
+        );
+
+        let raw = [
+            header(3, 1),
+            3_i32.to_be_bytes().to_vec(),
+            1_f32.to_be_bytes().to_vec(),
+        ]
+        .concat();
+        assert_eq!(Error::IndexOutOfBounds { index: 3, dim: 3 }, decode(&raw));
+
+        let raw = [
+            header(3, 2),
+            1_i32.to_be_bytes().to_vec(),
+            1_i32.to_be_bytes().to_vec(),
+            1_f32.to_be_bytes().to_vec(),
+            2_f32.to_be_bytes().to_vec(),
+        ]
+        .concat();
+        assert_eq!(Error::DuplicateIndex { index: 1 }, decode(&raw));
+
+        let raw = [
+            header(3, 2),
+            2_i32.to_be_bytes().to_vec(),
+            1_i32.to_be_bytes().to_vec(),
+            1_f32.to_be_bytes().to_vec(),
+            2_f32.to_be_bytes().to_vec(),
+        ]
+        .concat();
+        assert_eq!(Error::UnsortedIndices, decode(&raw));
+    }
+
     fn get_type(client: &mut Client, name: &str) -> Result<Type, postgres::Error> {
         let row = client.query_one("SELECT pg_type.oid, nspname AS schema FROM pg_type INNER JOIN pg_namespace ON pg_namespace.oid = pg_type.typnamespace WHERE typname = $1", &[&name])?;
         Ok(Type::new(
@@ -0,0 +1,209 @@
+use postgres_types::{Kind, Type};
+
+// uses the schema of the extension rather than the search path
+const QUERY: &str = "SELECT n.nspname AS schema, v.oid AS vector, v.typarray AS vector_array, h.oid AS halfvec, h.typarray AS halfvec_array, s.oid AS sparsevec, s.typarray AS sparsevec_array FROM pg_extension e INNER JOIN pg_namespace n ON n.oid = e.extnamespace INNER JOIN pg_type v ON v.typnamespace = e.extnamespace AND v.typname = 'vector' INNER JOIN pg_type h ON h.typnamespace = e.extnamespace AND h.typname = 'halfvec' INNER JOIN pg_type s ON s.typnamespace = e.extnamespace AND s.typname = 'sparsevec' WHERE e.extname = 'vector'";
+
+/// The types of the extension, for use with binary `COPY`.
+///
+/// Types are specific to a database, so resolve them once per connection (or pool) and clone them as needed.
+#[derive(Clone, Debug)]
+pub struct Types {
+    vector: Type,
+    vector_array: Type,
+    halfvec: Type,
+    halfvec_array: Type,
+    sparsevec: Type,
+    sparsevec_array: Type,
+}
+
+impl Types {
+    fn new(schema: String, oids: [u32; 6]) -> Types {
+        let [vector, vector_array, halfvec, halfvec_array, sparsevec, sparsevec_array] = oids;
+        let simple = |name: &str, oid| Type::new(name.into(), oid, Kind::Simple, schema.clone());
+        let array = |name: &str, oid, element: &Type| {
+            Type::new(
+                name.into(),
+                oid,
+                Kind::Array(element.clone()),
+                schema.clone(),
+            )
+        };
+
+        let vector = simple("vector", vector);
+        let halfvec = simple("halfvec", halfvec);
+        let sparsevec = simple("sparsevec", sparsevec);
+        Types {
+            vector_array: array("_vector", vector_array, &vector),
+            halfvec_array: array("_halfvec", halfvec_array, &halfvec),
+            sparsevec_array: array("_sparsevec", sparsevec_array, &sparsevec),
+            vector,
+            halfvec,
+            sparsevec,
+        }
+    }
+
+    /// Returns the `vector` type.
+    pub fn vector(&self) -> &Type {
+        &self.vector
+    }
+
+    /// Returns the `vector[]` type.
+    pub fn vector_array(&self) -> &Type {
+        &self.vector_array
+    }
+
+    /// Returns the `halfvec` type.
+    pub fn halfvec(&self) -> &Type {
+        &self.halfvec
+    }
+
+    /// Returns the `halfvec[]` type.
+    pub fn halfvec_array(&self) -> &Type {
+        &self.halfvec_array
+    }
+
+    /// Returns the `sparsevec` type.
+    pub fn sparsevec(&self) -> &Type {
+        &self.sparsevec
+    }
+
+    /// Returns the `sparsevec[]` type.
+    pub fn sparsevec_array(&self) -> &Type {
+        &self.sparsevec_array
+    }
+}
+
+/// Resolves the types of the extension (requires pgvector 0.7+).
+///
+/// Returns an error if the extension is not installed in the database.
+#[cfg(feature = "postgres-client")]
+pub fn types<C: ::postgres::GenericClient>(client: &mut C) -> Result<Types, ::postgres::Error> {
+    let row = client.query_one(QUERY, &[])?;
+    Ok(Types::new(
+        row.try_get("schema")?,
+        [
+            row.try_get("vector")?,
+            row.try_get("vector_array")?,
+            row.try_get("halfvec")?,
+            row.try_get("halfvec_array")?,
+            row.try_get("sparsevec")?,
+            row.try_get("sparsevec_array")?,
+        ],
+    ))
+}
+
+/// Resolves the types of the extension with tokio-postgres (requires pgvector 0.7+).
+///
+/// Returns an error if the extension is not installed in the database.
+#[cfg(feature = "tokio-postgres")]
+pub async fn types_async<C: ::tokio_postgres::GenericClient>(
+    client: &C,
+) -> Result<Types, ::tokio_postgres::Error> {
+    let row = client.query_one(QUERY, &[]).await?;
+    Ok(Types::new(
+        row.try_get("schema")?,
+        [
+            row.try_get("vector")?,
+            row.try_get("vector_array")?,
+            row.try_get("halfvec")?,
+            row.try_get("halfvec_array")?,
+            row.try_get("sparsevec")?,
+            row.try_get("sparsevec_array")?,
+        ],
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Types;
+    use postgres::types::Kind;
+
+    #[test]
+    fn test_new() {
+        let types = Types::new("public".into(), [1, 2, 3, 4, 5, 6]);
+        assert_eq!("vector", types.vector().name());
+        assert_eq!(1, types.vector().oid());
+        assert_eq!("public", types.vector().schema());
+        assert_eq!(&Kind::Simple, types.vector().kind());
+        assert_eq!("_vector", types.vector_array().name());
+        assert_eq!(2, types.vector_array().oid());
+        assert_eq!(
+            &Kind::Array(types.vector().clone()),
+            types.vector_array().kind()
+        );
+        assert_eq!(5, types.sparsevec().oid());
+        assert_eq!(6, types.sparsevec_array().oid());
+    }
+
+    #[cfg(feature = "postgres-client")]
+    #[test]
+    fn it_works() -> Result<(), postgres::Error> {
+        use crate::{SparseVector, Vector};
+        use postgres::binary_copy::BinaryCopyInWriter;
+        use postgres::{Client, NoTls};
+
+        let user = std::env::var("USER").unwrap();
+        let mut client = Client::configure()
+            .host("localhost")
+            .dbname("pgvector_rust_test")
+            .user(user.as_str())
+            .connect(NoTls)?;
+
+        client.execute("CREATE EXTENSION IF NOT EXISTS vector", &[])?;
+        client.execute("DROP TABLE IF EXISTS postgres_types_items", &[])?;
+        client.execute(
+            "CREATE TABLE postgres_types_items (id bigserial PRIMARY KEY, embedding vector(3), sparse_embedding sparsevec(3))",
+            &[],
+        )?;
+
+        let types = crate::postgres::types(&mut client)?;
+        assert_eq!("vector", types.vector().name());
+        assert_eq!("_vector", types.vector_array().name());
+
+        let vec = Vector::from(vec![1.0, 2.0, 3.0]);
+        let sparse_vec = SparseVector::from_dense(&[4.0, 0.0, 5.0]);
+        let writer = client.copy_in(
+            "COPY postgres_types_items (embedding, sparse_embedding) FROM STDIN WITH (FORMAT BINARY)",
+        )?;
+        let mut writer =
+            BinaryCopyInWriter::new(writer, &[types.vector().clone(), types.sparsevec().clone()]);
+        writer.write(&[&vec, &sparse_vec])?;
+        writer.finish()?;
+
+        let row = client.query_one(
+            "SELECT embedding, sparse_embedding FROM postgres_types_items",
+            &[],
+        )?;
+        assert_eq!(vec, row.get::<_, Vector>(0));
+        assert_eq!(sparse_vec, row.get::<_, SparseVector>(1));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "tokio-postgres")]
+    #[tokio::test]
+    async fn tokio_works() -> Result<(), tokio_postgres::Error> {
+        let (client, connection) = tokio_postgres::connect(
+            "host=localhost dbname=pgvector_rust_test",
+            tokio_postgres::NoTls,
+        )
+        .await?;
+
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                eprintln!("connection error: {}", e);
+            }
+        });
+
+        client
+            .execute("CREATE EXTENSION IF NOT EXISTS vector", &[])
+            .await?;
+        let types = crate::postgres::types_async(&client).await?;
+        assert_eq!("vector", types.vector().name());
+        assert_eq!("_vector", types.vector_array().name());
+        assert_eq!("halfvec", types.halfvec().name());
+        assert_eq!("sparsevec", types.sparsevec().name());
+
+        Ok(())
+    }
+}
@@ -3,11 +3,15 @@ use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
 use std::convert::TryInto;
 use std::error::Error;
 
-use crate::Vector;
+use crate::{FixedVector, Vector, VectorRef};
 
 impl<'a> FromSql<'a> for Vector {
     fn from_sql(_ty: &Type, raw: &'a [u8]) -> Result<Vector, Box<dyn Error + Sync + Send>> {
-        Vector::from_sql(raw)
+        // the binary format cannot start with this character due to dimension limits
+        if raw.first() == Some(&b'[') {
+            return Ok(std::str::from_utf8(raw)?.parse()?);
+        }
+        Ok(Vector::from_sql(raw)?)
     }
 
     fn accepts(ty: &Type) -> bool {
@@ -16,13 +20,41 @@ impl<'a> FromSql<'a> for Vector {
 }
 
 impl ToSql for Vector {
+    fn to_sql(&self, ty: &Type, w: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        VectorRef::from(self.as_slice()).to_sql(ty, w)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.name() == "vector"
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for VectorRef<'a> {
+    fn from_sql(_ty: &Type, raw: &'a [u8]) -> Result<VectorRef<'a>, Box<dyn Error + Sync + Send>> {
+        if raw.first() == Some(&b'[') {
+            return Err(crate::Error::TextFormat {
+                type_name: "vector",
+            }
+            .into());
+        }
+        Ok(VectorRef::from_sql(raw)?)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.name() == "vector"
+    }
+}
+
+impl ToSql for VectorRef<'_> {
     fn to_sql(&self, _ty: &Type, w: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
-        let dim = self.0.len();
+        let dim = self.len();
         w.put_u16(dim.try_into()?);
         w.put_u16(0);
 
-        for v in &self.0 {
-            w.put_f32(*v);
+        for v in self.iter() {
+            w.put_f32(v);
         }
 
         Ok(IsNull::No)
@@ -35,11 +67,39 @@ impl ToSql for Vector {
     to_sql_checked!();
 }
 
+impl<'a, const N: usize> FromSql<'a> for FixedVector<N> {
+    fn from_sql(_ty: &Type, raw: &'a [u8]) -> Result<FixedVector<N>, Box<dyn Error + Sync + Send>> {
+        // the binary format cannot start with this character due to dimension limits
+        if raw.first() == Some(&b'[') {
+            let vec: Vector = std::str::from_utf8(raw)?.parse()?;
+            return Ok(vec.try_into()?);
+        }
+        Ok(FixedVector::from_sql(raw)?)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.name() == "vector"
+    }
+}
+
+impl<const N: usize> ToSql for FixedVector<N> {
+    fn to_sql(&self, ty: &Type, w: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        VectorRef::from(self.as_slice()).to_sql(ty, w)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.name() == "vector"
+    }
+
+    to_sql_checked!();
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::Vector;
+    use crate::{Error, FixedVector, Vector, VectorRef};
+    use bytes::BytesMut;
     use postgres::binary_copy::BinaryCopyInWriter;
-    use postgres::types::{Kind, Type};
+    use postgres::types::{FromSql, Kind, ToSql, Type};
     use postgres::{Client, NoTls};
 
     #[test]
@@ -74,6 +134,20 @@ mod tests {
         assert_eq!(vec, res_vec);
         assert_eq!(vec![1.0, 2.0, 3.0], res_vec.to_vec());
 
+        let data = [1.0, 2.0, 3.0];
+        let row = client.query_one("SELECT $1::vector", &[&VectorRef::from(&data[..])])?;
+        let res_ref: VectorRef = row.get(0);
+        assert_eq!(VectorRef::from(&data[..]), res_ref);
+
+        let row = client.query_one(
+            "SELECT embedding FROM postgres_items ORDER BY id LIMIT 1",
+            &[],
+        )?;
+        let res_fixed: FixedVector<3> = row.get(0);
+        assert_eq!(FixedVector::from([1.0, 2.0, 3.0]), res_fixed);
+        let res_fixed: Result<FixedVector<2>, _> = row.try_get(0);
+        assert!(res_fixed.is_err());
+
         let empty_vec = Vector::from(vec![]);
         let empty_res = client.execute(
             "INSERT INTO postgres_items (embedding) VALUES ($1)",
@@ -99,6 +173,7 @@ mod tests {
         )?;
         let text_res: String = text_row.get(0);
         assert_eq!("[1,2,3]", text_res);
+        assert_eq!(vec, text_res.parse().unwrap());
 
         // copy
         let vector_type = get_type(&mut client, "vector")?;
@@ -112,6 +187,160 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(all(feature = "arrow", feature = "postgres-client"))]
+    #[test]
+    fn it_works_with_arrow() -> Result<(), postgres::Error> {
+        use crate::{Bit, SparseVector};
+
+        let user = std::env::var("USER").unwrap();
+        let mut client = Client::configure()
+            .host("localhost")
+            .dbname("pgvector_rust_test")
+            .user(user.as_str())
+            .connect(NoTls)?;
+
+        client.execute("CREATE EXTENSION IF NOT EXISTS vector", &[])?;
+        client.execute("DROP TABLE IF EXISTS postgres_arrow_items", &[])?;
+        client.execute(
+            "CREATE TABLE postgres_arrow_items (id bigserial PRIMARY KEY, embedding vector(3), sparse_embedding sparsevec(3), binary_embedding varbit)",
+            &[],
+        )?;
+
+        let vecs = vec![Some(Vector::from(vec![1.0, 2.0, 3.0])), None];
+        let sparse_vecs = vec![None, Some(SparseVector::from_dense(&[4.0, 0.0, 5.0]))];
+        let bits = vec![Some(Bit::new(&[true, false, true])), None];
+        let array = crate::arrow::to_fixed_size_list_array(&vecs).unwrap();
+        let sparse_array = crate::arrow::to_sparse_array(&sparse_vecs);
+        let bit_array = crate::arrow::to_bit_array(&bits).unwrap();
+
+        // rows are streamed, with vectors borrowed from the array
+        let vector_type = get_type(&mut client, "vector")?;
+        let sparsevec_type = get_type(&mut client, "sparsevec")?;
+        let writer = client.copy_in(
+            "COPY postgres_arrow_items (embedding, sparse_embedding, binary_embedding) FROM STDIN WITH (FORMAT BINARY)",
+        )?;
+        let mut writer =
+            BinaryCopyInWriter::new(writer, &[vector_type, sparsevec_type, Type::VARBIT]);
+        let refs = crate::arrow::refs_from_array(&array).unwrap();
+        let sparse_rows = crate::arrow::iter_sparse_array(&sparse_array).unwrap();
+        let bit_rows = crate::arrow::iter_bit_array_with_len(&bit_array, 3).unwrap();
+        for ((v, s), b) in refs.zip(sparse_rows).zip(bit_rows) {
+            writer.write(&[&v.unwrap(), &s.unwrap(), &b.unwrap()])?;
+        }
+        writer.finish()?;
+
+        let rows = client.query(
+            "SELECT embedding, sparse_embedding, binary_embedding FROM postgres_arrow_items ORDER BY id",
+            &[],
+        )?;
+        assert_eq!(2, rows.len());
+        assert_eq!(vecs[0], rows[0].get::<_, Option<Vector>>(0));
+        assert_eq!(None, rows[0].get::<_, Option<SparseVector>>(1));
+        assert_eq!(bits[0], rows[0].get::<_, Option<Bit>>(2));
+        assert_eq!(None, rows[1].get::<_, Option<Vector>>(0));
+        assert_eq!(sparse_vecs[1], rows[1].get::<_, Option<SparseVector>>(1));
+        assert_eq!(None, rows[1].get::<_, Option<Bit>>(2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_sql_text() {
+        let ty = Type::new("vector".into(), 0, Kind::Simple, "public".into());
+        let vec = <Vector as FromSql>::from_sql(&ty, b"[1,2,3]").unwrap();
+        assert_eq!(Vector::from(vec![1.0, 2.0, 3.0]), vec);
+
+        let err = <VectorRef as FromSql>::from_sql(&ty, b"[1,2,3]").unwrap_err();
+        assert_eq!(
+            Some(&Error::TextFormat {
+                type_name: "vector"
+            }),
+            err.downcast_ref::<Error>()
+        );
+    }
+
+    #[test]
+    fn test_to_sql_ref() {
+        let ty = Type::new("vector".into(), 0, Kind::Simple, "public".into());
+        let vec = Vector::from(vec![1.0, 2.0, 3.0]);
+        let mut buf = BytesMut::new();
+        vec.to_sql(&ty, &mut buf).unwrap();
+        let mut ref_buf = BytesMut::new();
+        VectorRef::from(&vec).to_sql(&ty, &mut ref_buf).unwrap();
+        assert_eq!(buf, ref_buf);
+
+        let vec_ref = <VectorRef as FromSql>::from_sql(&ty, &buf).unwrap();
+        assert_eq!(vec, vec_ref.to_vector());
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn test_to_sql_array_view() {
+        let ty = Type::new("vector".into(), 0, Kind::Simple, "public".into());
+        let vec = Vector::from(vec![1.0, 3.0]);
+        let mut buf = BytesMut::new();
+        vec.to_sql(&ty, &mut buf).unwrap();
+
+        let arr = ndarray::array![1.0, 2.0, 3.0];
+        let mut ref_buf = BytesMut::new();
+        VectorRef::from(arr.slice(ndarray::s![..;2]))
+            .to_sql(&ty, &mut ref_buf)
+            .unwrap();
+        assert_eq!(buf, ref_buf);
+    }
+
+    #[test]
+    fn test_fixed_vector() {
+        let ty = Type::new("vector".into(), 0, Kind::Simple, "public".into());
+        let vec = FixedVector::from([1.0, 2.0, 3.0]);
+        let mut buf = BytesMut::new();
+        vec.to_sql(&ty, &mut buf).unwrap();
+        assert_eq!(
+            vec,
+            <FixedVector<3> as FromSql>::from_sql(&ty, &buf).unwrap()
+        );
+        assert_eq!(
+            vec,
+            <FixedVector<3> as FromSql>::from_sql(&ty, b"[1,2,3]").unwrap()
+        );
+
+        let err = <FixedVector<2> as FromSql>::from_sql(&ty, &buf).unwrap_err();
+        assert_eq!("expected 2 dimensions, not 3", err.to_string());
+    }
+
+    #[test]
+    fn test_from_sql_invalid() {
+        let ty = Type::new("vector".into(), 0, Kind::Simple, "public".into());
+        let decode = |raw: &[u8]| {
+            let err = <Vector as FromSql>::from_sql(&ty, raw).unwrap_err();
+            err.downcast_ref::<Error>().unwrap().clone()
+        };
+
+        assert_eq!(
+            Error::Truncated {
+                type_name: "vector",
+                expected: 4,
+                actual: 2
+            },
+            decode(&[0, 1])
+        );
+        assert_eq!(
+            Error::NonZeroUnused {
+                type_name: "vector",
+                value: 1
+            },
+            decode(&[0, 1, 0, 1, 0, 0, 0, 0])
+        );
+        assert_eq!(
+            Error::InvalidLength {
+                type_name: "vector",
+                expected: 12,
+                actual: 8
+            },
+            decode(&[0, 2, 0, 0, 0, 0, 0, 0])
+        );
+    }
+
     fn get_type(client: &mut Client, name: &str) -> Result<Type, postgres::Error> {
         let row = client.query_one("SELECT pg_type.oid, nspname AS schema FROM pg_type INNER JOIN pg_namespace ON pg_namespace.oid = pg_type.typnamespace WHERE typname = $1", &[&name])?;
         Ok(Type::new(
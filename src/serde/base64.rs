@@ -0,0 +1,137 @@
+//! Serializes a type as a base64 string of its little-endian bytes.
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Item {
+//!     #[serde(with = "pgvector::serde::base64")]
+//!     embedding: Vector,
+//! }
+//! ```
+
+use serde::{de, Deserialize, Deserializer, Serializer};
+
+use super::LeBytes;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Serializes a value as base64.
+pub fn serialize<T: LeBytes, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&encode(&value.to_le_bytes()))
+}
+
+/// Deserializes a value from base64.
+pub fn deserialize<'de, T: LeBytes, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    let buf = decode(&s).ok_or_else(|| de::Error::custom("invalid base64"))?;
+    T::from_le_bytes(&buf).map_err(de::Error::custom)
+}
+
+// standard alphabet with padding
+fn encode(buf: &[u8]) -> String {
+    let mut s = String::with_capacity((buf.len() + 2) / 3 * 4);
+    for chunk in buf.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = u32::from(b[0]) << 16 | u32::from(b[1]) << 8 | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                s.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                s.push('=');
+            }
+        }
+    }
+    s
+}
+
+fn decode(s: &str) -> Option<Vec<u8>> {
+    let s = s.as_bytes();
+    if s.len() % 4 != 0 {
+        return None;
+    }
+
+    let mut buf = Vec::with_capacity(s.len() / 4 * 3);
+    for (i, chunk) in s.chunks(4).enumerate() {
+        let last = i == s.len() / 4 - 1;
+        let padding = chunk.iter().rev().take_while(|c| **c == b'=').count();
+        if padding > 2 || (padding > 0 && !last) {
+            return None;
+        }
+
+        let mut n = 0;
+        for c in &chunk[..4 - padding] {
+            let v = ALPHABET.iter().position(|a| a == c)? as u32;
+            n = n << 6 | v;
+        }
+        // reject non-canonical encodings with unused bits set
+        if n & ((1 << (2 * padding)) - 1) != 0 {
+            return None;
+        }
+        n <<= 6 * padding;
+
+        let bytes = n.to_be_bytes();
+        buf.extend_from_slice(&bytes[1..4 - padding]);
+    }
+    Some(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, encode};
+    use crate::{Bit, Vector};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct Item {
+        #[serde(with = "crate::serde::base64")]
+        embedding: Vector,
+        #[serde(with = "crate::serde::base64")]
+        binary_embedding: Bit,
+    }
+
+    #[test]
+    fn test_base64() {
+        let item = Item {
+            embedding: Vector::from(vec![1.0, 2.0]),
+            binary_embedding: Bit::new(&[true, false, true]),
+        };
+        let json = serde_json::to_string(&item).unwrap();
+        assert_eq!(
+            r#"{"embedding":"AACAPwAAAEA=","binary_embedding":"AwAAAKA="}"#,
+            json
+        );
+        assert_eq!(item, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn test_base64_invalid() {
+        let json = r#"{"embedding":"AACAPwAAAE","binary_embedding":"AwAAAKA="}"#;
+        let err = serde_json::from_str::<Item>(json).unwrap_err();
+        assert!(err.to_string().starts_with("invalid base64"));
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        for (input, output) in [
+            (&b""[..], ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg=="),
+            (b"fooba", "Zm9vYmE="),
+            (b"foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(output, encode(input));
+            assert_eq!(Some(input.to_vec()), decode(output));
+        }
+        assert_eq!(None, decode("Zm9"));
+        assert_eq!(None, decode("Zg==Zg=="));
+        assert_eq!(None, decode("Z==="));
+        assert_eq!(None, decode("Zm9!"));
+        assert_eq!(None, decode("Zh=="));
+        assert_eq!(None, decode("Zm9="));
+    }
+}
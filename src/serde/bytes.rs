@@ -0,0 +1,163 @@
+//! Serializes a type as little-endian bytes, for binary formats like bincode and postcard.
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Item {
+//!     #[serde(with = "pgvector::serde::bytes")]
+//!     embedding: Vector,
+//! }
+//! ```
+
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserializer, Serializer};
+use std::fmt;
+use std::marker::PhantomData;
+
+use super::LeBytes;
+
+/// Serializes a value as bytes.
+pub fn serialize<T: LeBytes, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bytes(&value.to_le_bytes())
+}
+
+/// Deserializes a value from bytes.
+pub fn deserialize<'de, T: LeBytes, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+    deserializer.deserialize_byte_buf(BytesVisitor(PhantomData))
+}
+
+struct BytesVisitor<T>(PhantomData<T>);
+
+impl<'de, T: LeBytes> Visitor<'de> for BytesVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("bytes")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<T, E> {
+        T::from_le_bytes(v).map_err(E::custom)
+    }
+
+    // for formats without a bytes type, like JSON
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+        // cap the hint since it comes from the input
+        let mut buf = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(v) = seq.next_element()? {
+            buf.push(v);
+        }
+        self.visit_bytes(&buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bit, Vector};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct Item {
+        #[serde(with = "crate::serde::bytes")]
+        embedding: Vector,
+        #[serde(with = "crate::serde::bytes")]
+        binary_embedding: Bit,
+    }
+
+    #[test]
+    fn test_bytes() {
+        let item = Item {
+            embedding: Vector::from(vec![1.0, 2.0]),
+            binary_embedding: Bit::new(&[true, false, true]),
+        };
+        let json = serde_json::to_string(&item).unwrap();
+        assert_eq!(
+            r#"{"embedding":[0,0,128,63,0,0,0,64],"binary_embedding":[3,0,0,0,160]}"#,
+            json
+        );
+        assert_eq!(item, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn test_bytes_deserializer() {
+        use serde::de::value::{BytesDeserializer, Error};
+
+        let deserializer = BytesDeserializer::<Error>::new(&[0, 0, 128, 63]);
+        let vec: Vector = super::deserialize(deserializer).unwrap();
+        assert_eq!(Vector::from(vec![1.0]), vec);
+    }
+
+    #[test]
+    fn test_bytes_size_hint() {
+        use serde::de::value::{Error, U8Deserializer};
+        use serde::de::{DeserializeSeed, SeqAccess, Visitor};
+
+        // a sequence with a length prefix that does not match the data
+        struct Seq(Vec<u8>);
+
+        impl<'de> SeqAccess<'de> for Seq {
+            type Error = Error;
+
+            fn next_element_seed<T: DeserializeSeed<'de>>(
+                &mut self,
+                seed: T,
+            ) -> Result<Option<T::Value>, Error> {
+                if self.0.is_empty() {
+                    return Ok(None);
+                }
+                seed.deserialize(U8Deserializer::new(self.0.remove(0)))
+                    .map(Some)
+            }
+
+            fn size_hint(&self) -> Option<usize> {
+                Some(usize::MAX)
+            }
+        }
+
+        let visitor = super::BytesVisitor::<Vector>(std::marker::PhantomData);
+        let vec = visitor.visit_seq(Seq(vec![0, 0, 128, 63])).unwrap();
+        assert_eq!(Vector::from(vec![1.0]), vec);
+    }
+
+    #[test]
+    fn test_bytes_invalid() {
+        let json = r#"{"embedding":[0,0,128],"binary_embedding":[3,0,0,0,160]}"#;
+        let err = serde_json::from_str::<Item>(json).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("invalid binary representation of vector: expected 4 bytes, got 3"));
+
+        let json = r#"{"embedding":[0,0,128,63],"binary_embedding":[9,0,0,0,160]}"#;
+        let err = serde_json::from_str::<Item>(json).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("invalid binary representation of bit: expected 6 bytes, got 5"));
+
+        let json = r#"{"embedding":[],"binary_embedding":[3,0,0,0,160]}"#;
+        let err = serde_json::from_str::<Item>(json).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("vector must have at least 1 dimension"));
+
+        let json = r#"{"embedding":[0,0,192,127],"binary_embedding":[3,0,0,0,160]}"#;
+        let err = serde_json::from_str::<Item>(json).unwrap_err();
+        assert!(err.to_string().starts_with("NaN not allowed in vector"));
+    }
+
+    #[cfg(feature = "halfvec")]
+    #[test]
+    fn test_bytes_halfvec() {
+        use crate::HalfVector;
+
+        #[derive(Debug, PartialEq, Deserialize, Serialize)]
+        struct Item {
+            #[serde(with = "crate::serde::bytes")]
+            embedding: HalfVector,
+        }
+
+        let item = Item {
+            embedding: HalfVector::from_f32_slice(&[1.0, 2.0]),
+        };
+        let json = serde_json::to_string(&item).unwrap();
+        assert_eq!(r#"{"embedding":[0,60,0,64]}"#, json);
+        assert_eq!(item, serde_json::from_str(&json).unwrap());
+    }
+}
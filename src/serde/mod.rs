@@ -0,0 +1,113 @@
+//! Adapters for use with `#[serde(with = "...")]`.
+//!
+//! The `bytes` and `base64` adapters use a compact little-endian representation:
+//! values for vectors and half vectors, and a 4-byte length followed by the data
+//! for bit strings.
+
+pub mod base64;
+pub mod bytes;
+pub mod text;
+
+use crate::{Bit, Error, Vector};
+
+#[cfg(feature = "halfvec")]
+use crate::HalfVector;
+
+#[cfg(feature = "halfvec")]
+use half::f16;
+
+/// A type with a little-endian binary representation.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait LeBytes: private::Sealed + Sized {
+    #[doc(hidden)]
+    fn to_le_bytes(&self) -> Vec<u8>;
+
+    #[doc(hidden)]
+    fn from_le_bytes(buf: &[u8]) -> Result<Self, Error>;
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for crate::Vector {}
+    impl Sealed for crate::Bit {}
+
+    #[cfg(feature = "halfvec")]
+    impl Sealed for crate::HalfVector {}
+}
+
+impl LeBytes for Vector {
+    fn to_le_bytes(&self) -> Vec<u8> {
+        self.0.iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+
+    fn from_le_bytes(buf: &[u8]) -> Result<Self, Error> {
+        check_len(buf, 4, "vector")?;
+        Vector::try_new(
+            buf.chunks_exact(4)
+                .map(|v| f32::from_le_bytes([v[0], v[1], v[2], v[3]]))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(feature = "halfvec")]
+impl LeBytes for HalfVector {
+    fn to_le_bytes(&self) -> Vec<u8> {
+        self.0.iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+
+    fn from_le_bytes(buf: &[u8]) -> Result<Self, Error> {
+        check_len(buf, 2, "halfvec")?;
+        HalfVector::try_new(
+            buf.chunks_exact(2)
+                .map(|v| f16::from_le_bytes([v[0], v[1]]))
+                .collect(),
+        )
+    }
+}
+
+impl LeBytes for Bit {
+    fn to_le_bytes(&self) -> Vec<u8> {
+        // length cannot exceed i32::MAX
+        let mut buf = Vec::with_capacity(4 + self.data.len());
+        buf.extend_from_slice(&(self.len as u32).to_le_bytes());
+        buf.extend_from_slice(&self.data);
+        buf
+    }
+
+    fn from_le_bytes(buf: &[u8]) -> Result<Self, Error> {
+        if buf.len() < 4 {
+            return Err(Error::Truncated {
+                type_name: "bit",
+                expected: 4,
+                actual: buf.len(),
+            });
+        }
+        let len = u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
+        Bit::from_bytes_with_len(&buf[4..], len).map_err(|e| match e {
+            Error::InvalidLength {
+                type_name,
+                expected,
+                actual,
+            } => Error::InvalidLength {
+                type_name,
+                expected: expected + 4,
+                actual: actual + 4,
+            },
+            e => e,
+        })
+    }
+}
+
+fn check_len(buf: &[u8], size: usize, type_name: &'static str) -> Result<(), Error> {
+    if buf.len() % size != 0 {
+        return Err(Error::InvalidLength {
+            type_name,
+            expected: buf.len() + size - buf.len() % size,
+            actual: buf.len(),
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,69 @@
+//! Serializes a type as its pgvector text representation.
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Item {
+//!     #[serde(with = "pgvector::serde::text")]
+//!     embedding: Vector,
+//! }
+//! ```
+
+use serde::{de, Deserialize, Deserializer, Serializer};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Serializes a value as text.
+pub fn serialize<T: Display, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+/// Deserializes a value from text.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: FromStr,
+    T::Err: Display,
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    s.parse().map_err(de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bit, SparseVector, Vector};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct Item {
+        #[serde(with = "crate::serde::text")]
+        embedding: Vector,
+        #[serde(with = "crate::serde::text")]
+        sparse_embedding: SparseVector,
+        #[serde(with = "crate::serde::text")]
+        binary_embedding: Bit,
+    }
+
+    #[test]
+    fn test_text() {
+        let item = Item {
+            embedding: Vector::from(vec![1.0, 2.0, 3.0]),
+            sparse_embedding: SparseVector::from_dense(&[1.0, 0.0, 2.0]),
+            binary_embedding: Bit::new(&[true, false, true]),
+        };
+        let json = serde_json::to_string(&item).unwrap();
+        assert_eq!(
+            r#"{"embedding":"[1,2,3]","sparse_embedding":"{1:1,3:2}/3","binary_embedding":"101"}"#,
+            json
+        );
+        assert_eq!(item, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn test_text_invalid() {
+        let json = r#"{"embedding":"[1,2","sparse_embedding":"{}/3","binary_embedding":"101"}"#;
+        let err = serde_json::from_str::<Item>(json).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("invalid input syntax for type vector"));
+    }
+}
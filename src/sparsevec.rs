@@ -4,10 +4,27 @@ use crate::diesel_ext::sparsevec::SparseVectorType;
 #[cfg(feature = "diesel")]
 use diesel::{deserialize::FromSqlRow, expression::AsExpression};
 
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::distance;
+use crate::limits;
+use crate::ops;
+use crate::text::{self, Scanner};
+use crate::vector::VECTOR_MAX_DIM;
+use crate::{Error, Vector};
+
+pub(crate) const SPARSEVEC_MAX_DIM: i32 = 1_000_000_000;
+pub(crate) const SPARSEVEC_MAX_NNZ: usize = 16000;
+
 /// A sparse vector.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "diesel", derive(FromSqlRow, AsExpression))]
 #[cfg_attr(feature = "diesel", diesel(sql_type = SparseVectorType))]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SparseVector {
     pub(crate) dim: i32,
     pub(crate) indices: Vec<i32>,
@@ -15,6 +32,59 @@ pub struct SparseVector {
 }
 
 impl SparseVector {
+    /// Creates a sparse vector from indices and non-zero values, checking the same limits as the server.
+    pub fn try_new(dim: i32, indices: Vec<i32>, values: Vec<f32>) -> Result<SparseVector, Error> {
+        if indices.len() != values.len() {
+            return Err(Error::MismatchedLengths {
+                indices: indices.len(),
+                values: values.len(),
+            });
+        }
+        if dim < 1 {
+            return Err(Error::TooFewDimensions {
+                type_name: "sparsevec",
+            });
+        }
+        if dim > SPARSEVEC_MAX_DIM {
+            return Err(Error::TooManyDimensions {
+                type_name: "sparsevec",
+                max: SPARSEVEC_MAX_DIM as usize,
+            });
+        }
+        if indices.len() > SPARSEVEC_MAX_NNZ {
+            return Err(Error::TooManyNonZero {
+                max: SPARSEVEC_MAX_NNZ,
+            });
+        }
+
+        for (i, (index, value)) in indices.iter().zip(&values).enumerate() {
+            if *index < 0 || *index >= dim {
+                return Err(Error::IndexOutOfBounds {
+                    index: i64::from(*index),
+                    dim,
+                });
+            }
+            if i > 0 {
+                if *index < indices[i - 1] {
+                    return Err(Error::UnsortedIndices);
+                }
+                if *index == indices[i - 1] {
+                    return Err(Error::DuplicateIndex { index: *index });
+                }
+            }
+            text::check_element(*value, "sparsevec")?;
+            if *value == 0.0 {
+                return Err(Error::ZeroValue { index: *index });
+            }
+        }
+
+        Ok(SparseVector {
+            dim,
+            indices,
+            values,
+        })
+    }
+
     /// Creates a sparse vector from a dense vector.
     pub fn from_dense(vec: &[f32]) -> SparseVector {
         let dim: i32 = vec.len().try_into().unwrap();
@@ -76,36 +146,347 @@ impl SparseVector {
         vec
     }
 
+    /// Returns the Euclidean norm, like the `l2_norm` function.
+    pub fn norm(&self) -> f64 {
+        self.values
+            .iter()
+            .map(|v| f64::from(*v) * f64::from(*v))
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// Returns the taxicab norm.
+    pub fn l1_norm(&self) -> f64 {
+        self.values.iter().map(|v| f64::from(v.abs())).sum()
+    }
+
+    /// Returns the value at an index, or `None` if out of bounds.
+    ///
+    /// Indices start at 0.
+    pub fn get(&self, index: usize) -> Option<f32> {
+        if index >= usize::try_from(self.dim).unwrap_or(0) {
+            return None;
+        }
+        let index = index as i32;
+        match self.indices.binary_search(&index) {
+            Ok(i) => Some(self.values[i]),
+            Err(_) => Some(0.0),
+        }
+    }
+
+    /// Returns a sparse vector with only the `k` values with the largest absolute values.
+    ///
+    /// Ties are broken by the lower index, and NaN values rank above all others.
+    pub fn top_k(&self, k: usize) -> SparseVector {
+        if self.values.len() <= k {
+            return self.clone();
+        }
+
+        // stable to prefer lower indices on ties
+        // the bits of absolute values have the same order as the values, with NaN last
+        let mut positions: Vec<usize> = (0..self.values.len()).collect();
+        positions.sort_by_key(|&i| std::cmp::Reverse(self.values[i].abs().to_bits()));
+        positions.truncate(k);
+        positions.sort_unstable();
+
+        SparseVector {
+            dim: self.dim,
+            indices: positions.iter().map(|&i| self.indices[i]).collect(),
+            values: positions.iter().map(|&i| self.values[i]).collect(),
+        }
+    }
+
+    /// Returns the sparse vector normalized to unit length, like the `l2_normalize` function.
+    ///
+    /// A zero sparse vector stays zero.
+    pub fn l2_normalize(&self) -> SparseVector {
+        let norm = self.norm();
+        if norm == 0.0 {
+            return self.clone();
+        }
+
+        let mut indices = Vec::with_capacity(self.indices.len());
+        let mut values = Vec::with_capacity(self.values.len());
+        for (i, v) in self.indices.iter().zip(&self.values) {
+            let v = (f64::from(*v) / norm) as f32;
+            // drop values that underflow
+            if v != 0.0 {
+                indices.push(*i);
+                values.push(v);
+            }
+        }
+
+        SparseVector {
+            dim: self.dim,
+            indices,
+            values,
+        }
+    }
+
+    /// Returns the L2 distance to another sparse vector, like the `<->` operator.
+    pub fn l2_distance(&self, other: &SparseVector) -> Result<f64, Error> {
+        self.check_dims(other)?;
+        let distance = distance::sparse_l2_squared_distance(
+            &self.indices,
+            &self.values,
+            &other.indices,
+            &other.values,
+        );
+        Ok(f64::from(distance).sqrt())
+    }
+
+    /// Returns the inner product with another sparse vector.
+    pub fn inner_product(&self, other: &SparseVector) -> Result<f64, Error> {
+        self.check_dims(other)?;
+        Ok(f64::from(distance::sparse_inner_product(
+            &self.indices,
+            &self.values,
+            &other.indices,
+            &other.values,
+        )))
+    }
+
+    /// Returns the negative inner product with another sparse vector, like the `<#>` operator.
+    pub fn max_inner_product(&self, other: &SparseVector) -> Result<f64, Error> {
+        self.inner_product(other).map(|v| -v)
+    }
+
+    /// Returns the cosine distance to another sparse vector, like the `<=>` operator.
+    pub fn cosine_distance(&self, other: &SparseVector) -> Result<f64, Error> {
+        let similarity = self.inner_product(other)?;
+        let norma = distance::squared_norm(&self.values);
+        let normb = distance::squared_norm(&other.values);
+
+        // use sqrt(a * b) over sqrt(a) * sqrt(b)
+        let similarity = similarity / (f64::from(norma) * f64::from(normb)).sqrt();
+        Ok(distance::cosine_distance(similarity))
+    }
+
+    /// Returns the L1 distance to another sparse vector, like the `<+>` operator.
+    pub fn l1_distance(&self, other: &SparseVector) -> Result<f64, Error> {
+        self.check_dims(other)?;
+        Ok(f64::from(distance::sparse_l1_distance(
+            &self.indices,
+            &self.values,
+            &other.indices,
+            &other.values,
+        )))
+    }
+
+    fn try_add(&self, other: &SparseVector) -> Result<SparseVector, Error> {
+        self.merge_with(other, |a, b| ops::check_result(a + b))
+    }
+
+    fn try_sub(&self, other: &SparseVector) -> Result<SparseVector, Error> {
+        self.merge_with(other, |a, b| ops::check_result(a - b))
+    }
+
+    fn try_mul(&self, other: &SparseVector) -> Result<SparseVector, Error> {
+        self.merge_with(other, |a, b| ops::check_product(a * b, a, b))
+    }
+
+    fn try_scale(&self, scalar: f32) -> Result<SparseVector, Error> {
+        let mut indices = Vec::with_capacity(self.indices.len());
+        let mut values = Vec::with_capacity(self.values.len());
+        for (i, &a) in self.indices.iter().zip(&self.values) {
+            let v = ops::check_product(a * scalar, a, scalar)?;
+            if v != 0.0 {
+                indices.push(*i);
+                values.push(v);
+            }
+        }
+        Ok(SparseVector {
+            dim: self.dim,
+            indices,
+            values,
+        })
+    }
+
+    // merges the sorted indices, treating missing elements as zero and
+    // dropping zero results
+    fn merge_with<F: Fn(f32, f32) -> Result<f32, Error>>(
+        &self,
+        other: &SparseVector,
+        f: F,
+    ) -> Result<SparseVector, Error> {
+        self.check_dims(other)?;
+
+        let mut indices = Vec::new();
+        let mut values = Vec::new();
+        let mut i = 0;
+        let mut j = 0;
+        while i < self.indices.len() || j < other.indices.len() {
+            let a = self.indices.get(i).copied().unwrap_or(i32::MAX);
+            let b = other.indices.get(j).copied().unwrap_or(i32::MAX);
+            let (index, v) = if a < b {
+                i += 1;
+                (a, f(self.values[i - 1], 0.0)?)
+            } else if a > b {
+                j += 1;
+                (b, f(0.0, other.values[j - 1])?)
+            } else {
+                i += 1;
+                j += 1;
+                (a, f(self.values[i - 1], other.values[j - 1])?)
+            };
+            if v != 0.0 {
+                if values.len() == SPARSEVEC_MAX_NNZ {
+                    return Err(Error::TooManyNonZero {
+                        max: SPARSEVEC_MAX_NNZ,
+                    });
+                }
+                indices.push(index);
+                values.push(v);
+            }
+        }
+
+        Ok(SparseVector {
+            dim: self.dim,
+            indices,
+            values,
+        })
+    }
+
+    fn check_dims(&self, other: &SparseVector) -> Result<(), Error> {
+        if self.dim != other.dim {
+            return Err(Error::DifferentDimensions {
+                type_name: "sparsevec",
+                left: self.dim.try_into().unwrap_or(0),
+                right: other.dim.try_into().unwrap_or(0),
+            });
+        }
+        Ok(())
+    }
+
+    // creates a sparse vector from dense elements like the server casts
+    pub(crate) fn try_from_dense<I: ExactSizeIterator<Item = f32>>(
+        elements: I,
+    ) -> Result<SparseVector, Error> {
+        let dim = elements.len();
+        limits::check_dims(dim, "sparsevec", SPARSEVEC_MAX_DIM as usize)?;
+
+        let mut indices = Vec::new();
+        let mut values = Vec::new();
+        for (i, v) in elements.enumerate() {
+            if v != 0.0 {
+                if values.len() == SPARSEVEC_MAX_NNZ {
+                    return Err(Error::TooManyNonZero {
+                        max: SPARSEVEC_MAX_NNZ,
+                    });
+                }
+                indices.push(i as i32);
+                values.push(v);
+            }
+        }
+
+        Ok(SparseVector {
+            dim: dim as i32,
+            indices,
+            values,
+        })
+    }
+
+    // converts to dense elements like the server casts
+    pub(crate) fn try_to_dense(
+        &self,
+        type_name: &'static str,
+        max_dim: usize,
+    ) -> Result<Vec<f32>, Error> {
+        if self.dim < 1 {
+            return Err(Error::TooFewDimensions { type_name });
+        }
+        let dim = self.dim as usize;
+        if dim > max_dim {
+            return Err(Error::TooManyDimensions {
+                type_name,
+                max: max_dim,
+            });
+        }
+
+        let mut vec = vec![0.0; dim];
+        for (i, v) in self.indices.iter().zip(&self.values) {
+            if *i < 0 || *i >= self.dim {
+                return Err(Error::IndexOutOfBounds {
+                    index: (*i).into(),
+                    dim: self.dim,
+                });
+            }
+            vec[*i as usize] = *v;
+        }
+        Ok(vec)
+    }
+
     #[cfg(any(feature = "postgres", feature = "sqlx", feature = "diesel"))]
-    pub(crate) fn from_sql(
-        buf: &[u8],
-    ) -> Result<SparseVector, Box<dyn std::error::Error + Sync + Send>> {
+    pub(crate) fn from_sql(buf: &[u8]) -> Result<SparseVector, Error> {
         if buf.len() < 12 {
-            return Err("invalid length".into());
+            return Err(Error::Truncated {
+                type_name: "sparsevec",
+                expected: 12,
+                actual: buf.len(),
+            });
+        }
+
+        let read_i32 = |s: usize| i32::from_be_bytes([buf[s], buf[s + 1], buf[s + 2], buf[s + 3]]);
+
+        let dim = read_i32(0);
+        if dim < 0 {
+            return Err(Error::DimensionOverflow {
+                type_name: "sparsevec",
+                value: dim.into(),
+            });
         }
 
-        let dim = i32::from_be_bytes(buf[0..4].try_into()?);
-        let nnz = i32::from_be_bytes(buf[4..8].try_into()?).try_into()?;
-        let unused = i32::from_be_bytes(buf[8..12].try_into()?);
+        let nnz = read_i32(4);
+        let overflow = Error::DimensionOverflow {
+            type_name: "sparsevec",
+            value: nnz.into(),
+        };
+        let nnz = usize::try_from(nnz).map_err(|_| overflow.clone())?;
+
+        let unused = read_i32(8);
         if unused != 0 {
-            return Err("expected unused to be 0".into());
+            return Err(Error::NonZeroUnused {
+                type_name: "sparsevec",
+                value: unused,
+            });
         }
 
-        if (buf.len() - 12) / 8 != nnz || (buf.len() - 12) % 8 != 0 {
-            return Err("invalid length".into());
+        let expected = nnz
+            .checked_mul(8)
+            .and_then(|v| v.checked_add(12))
+            .ok_or(overflow)?;
+        if buf.len() != expected {
+            return Err(Error::InvalidLength {
+                type_name: "sparsevec",
+                expected,
+                actual: buf.len(),
+            });
         }
 
         let mut indices = Vec::with_capacity(nnz);
         for i in 0..nnz {
-            let s = 12 + 4 * i;
-            indices.push(i32::from_be_bytes(buf[s..s + 4].try_into()?));
+            let index = read_i32(12 + 4 * i);
+            if index < 0 || index >= dim {
+                return Err(Error::IndexOutOfBounds {
+                    index: index.into(),
+                    dim,
+                });
+            }
+            if i > 0 {
+                if index < indices[i - 1] {
+                    return Err(Error::UnsortedIndices);
+                }
+                if index == indices[i - 1] {
+                    return Err(Error::DuplicateIndex { index });
+                }
+            }
+            indices.push(index);
         }
 
-        let mut values = Vec::with_capacity(nnz);
-        for i in 0..nnz {
-            let s = 12 + 4 * nnz + 4 * i;
-            values.push(f32::from_be_bytes(buf[s..s + 4].try_into()?));
-        }
+        let values = buf[12 + 4 * nnz..]
+            .chunks_exact(4)
+            .map(|v| f32::from_be_bytes([v[0], v[1], v[2], v[3]]))
+            .collect();
 
         Ok(SparseVector {
             dim,
@@ -115,9 +496,182 @@ impl SparseVector {
     }
 }
 
+ops::impl_ops!(SparseVector, f32);
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for SparseVector {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename = "SparseVector", deny_unknown_fields)]
+        struct Data {
+            dim: i32,
+            indices: Vec<i32>,
+            values: Vec<f32>,
+        }
+
+        let data = Data::deserialize(deserializer)?;
+        SparseVector::try_new(data.dim, data.indices, data.values).map_err(de::Error::custom)
+    }
+}
+
+impl TryFrom<&Vector> for SparseVector {
+    type Error = Error;
+
+    fn try_from(vec: &Vector) -> Result<Self, Self::Error> {
+        SparseVector::try_from_dense(vec.as_slice().iter().copied())
+    }
+}
+
+impl TryFrom<Vector> for SparseVector {
+    type Error = Error;
+
+    fn try_from(vec: Vector) -> Result<Self, Self::Error> {
+        SparseVector::try_from(&vec)
+    }
+}
+
+impl TryFrom<&SparseVector> for Vector {
+    type Error = Error;
+
+    fn try_from(vec: &SparseVector) -> Result<Self, Self::Error> {
+        Ok(Vector::from(vec.try_to_dense("vector", VECTOR_MAX_DIM)?))
+    }
+}
+
+impl TryFrom<SparseVector> for Vector {
+    type Error = Error;
+
+    fn try_from(vec: SparseVector) -> Result<Self, Self::Error> {
+        Vector::try_from(&vec)
+    }
+}
+
+impl fmt::Display for SparseVector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("{")?;
+        for (i, (index, value)) in self.indices.iter().zip(&self.values).enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}:", i64::from(*index) + 1)?;
+            text::write_float(f, *value)?;
+        }
+        write!(f, "}}/{}", self.dim)
+    }
+}
+
+impl FromStr for SparseVector {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let syntax = Error::InvalidSyntax {
+            type_name: "sparsevec",
+        };
+
+        let mut scanner = Scanner::new(s);
+        scanner.skip_space();
+        if !scanner.eat(b'{') {
+            return Err(syntax);
+        }
+
+        let mut elements: Vec<(i32, f32)> = Vec::new();
+        scanner.skip_space();
+        if !scanner.eat(b'}') {
+            loop {
+                scanner.skip_space();
+                let token = scanner.int().ok_or_else(|| syntax.clone())?;
+                let index = parse_i32(token).ok_or_else(|| Error::OutOfRange {
+                    type_name: "sparsevec",
+                    value: token.to_string(),
+                })?;
+
+                scanner.skip_space();
+                if !scanner.eat(b':') {
+                    return Err(syntax);
+                }
+                scanner.skip_space();
+
+                let value = scanner.float("sparsevec")?;
+                text::check_element(value, "sparsevec")?;
+
+                // do not store zero values
+                if value != 0.0 {
+                    // convert to 0-based index
+                    elements.push((index.wrapping_sub(1), value));
+                }
+
+                scanner.skip_space();
+                if scanner.eat(b',') {
+                    continue;
+                }
+                if scanner.eat(b'}') {
+                    break;
+                }
+                return Err(syntax);
+            }
+        }
+
+        if !scanner.eat(b'/') {
+            return Err(syntax);
+        }
+        scanner.skip_space();
+        let token = scanner.int().ok_or_else(|| syntax.clone())?;
+        let dim = match parse_i32(token) {
+            Some(dim) => dim,
+            None if token.starts_with('-') => i32::MIN,
+            None => i32::MAX,
+        };
+
+        scanner.skip_space();
+        if !scanner.is_done() {
+            return Err(syntax);
+        }
+
+        if dim < 1 {
+            return Err(Error::TooFewDimensions {
+                type_name: "sparsevec",
+            });
+        }
+        if dim > SPARSEVEC_MAX_DIM {
+            return Err(Error::TooManyDimensions {
+                type_name: "sparsevec",
+                max: SPARSEVEC_MAX_DIM as usize,
+            });
+        }
+        if elements.len() > SPARSEVEC_MAX_NNZ {
+            return Err(Error::TooManyNonZero {
+                max: SPARSEVEC_MAX_NNZ,
+            });
+        }
+
+        elements.sort_by_key(|v| v.0);
+        for (i, (index, _)) in elements.iter().enumerate() {
+            if *index < 0 || *index >= dim {
+                return Err(Error::IndexOutOfBounds {
+                    index: i64::from(*index),
+                    dim,
+                });
+            }
+            if i > 0 && *index == elements[i - 1].0 {
+                return Err(Error::DuplicateIndex { index: *index });
+            }
+        }
+
+        Ok(SparseVector {
+            dim,
+            indices: elements.iter().map(|v| v.0).collect(),
+            values: elements.iter().map(|v| v.1).collect(),
+        })
+    }
+}
+
+fn parse_i32(token: &str) -> Option<i32> {
+    token.parse::<i64>().ok()?.try_into().ok()
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::SparseVector;
+    use crate::{Error, SparseVector, Vector};
     use std::collections::{BTreeMap, HashMap};
 
     #[test]
@@ -150,6 +704,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::useless_vec)]
     fn test_from_vec_map() {
         let vec = vec![(0, 1.0), (2, 2.0), (4, 3.0)];
         let map = vec.iter().map(|v| (&v.0, &v.1));
@@ -159,4 +714,349 @@ mod tests {
         assert_eq!(&[0, 2, 4], vec.indices());
         assert_eq!(&[1.0, 2.0, 3.0], vec.values());
     }
+
+    #[test]
+    fn test_display() {
+        let vec = SparseVector::from_dense(&[1.0, 0.0, 2.0, 0.0, 3.0, 0.0]);
+        assert_eq!("{1:1,3:2,5:3}/6", vec.to_string());
+
+        let vec = SparseVector::from_dense(&[0.0, 0.0]);
+        assert_eq!("{}/2", vec.to_string());
+    }
+
+    #[test]
+    fn test_from_str() {
+        let vec: SparseVector = "{1:1,3:2,5:3}/6".parse().unwrap();
+        assert_eq!(
+            SparseVector::from_dense(&[1.0, 0.0, 2.0, 0.0, 3.0, 0.0]),
+            vec
+        );
+
+        let vec: SparseVector = " { 5 : 3 , 1:1, 3:0 }/ 6 ".parse().unwrap();
+        assert_eq!(
+            SparseVector::from_dense(&[1.0, 0.0, 0.0, 0.0, 3.0, 0.0]),
+            vec
+        );
+
+        let vec: SparseVector = "{}/3".parse().unwrap();
+        assert_eq!(SparseVector::from_dense(&[0.0, 0.0, 0.0]), vec);
+    }
+
+    #[test]
+    fn test_from_str_round_trip() {
+        let vec = SparseVector::from_dense(&[0.1, 0.0, 1.0 / 3.0, 0.0, -1e-30]);
+        assert_eq!(vec, vec.to_string().parse().unwrap());
+    }
+
+    #[test]
+    fn test_from_str_invalid() {
+        let syntax = Error::InvalidSyntax {
+            type_name: "sparsevec",
+        };
+        assert_eq!(Err(syntax.clone()), "{1:1}".parse::<SparseVector>());
+        assert_eq!(Err(syntax.clone()), "{1:1/2".parse::<SparseVector>());
+        assert_eq!(Err(syntax.clone()), "{1}/2".parse::<SparseVector>());
+        assert_eq!(Err(syntax), "{1:1}/2 x".parse::<SparseVector>());
+        assert_eq!(
+            Err(Error::TooFewDimensions {
+                type_name: "sparsevec"
+            }),
+            "{}/0".parse::<SparseVector>()
+        );
+        assert_eq!(
+            Err(Error::TooManyDimensions {
+                type_name: "sparsevec",
+                max: 1000000000
+            }),
+            "{}/1000000001".parse::<SparseVector>()
+        );
+        assert_eq!(
+            Err(Error::IndexOutOfBounds { index: -1, dim: 3 }),
+            "{0:1}/3".parse::<SparseVector>()
+        );
+        assert_eq!(
+            Err(Error::IndexOutOfBounds { index: 3, dim: 3 }),
+            "{4:1}/3".parse::<SparseVector>()
+        );
+        assert_eq!(
+            Err(Error::DuplicateIndex { index: 0 }),
+            "{1:1,1:2}/3".parse::<SparseVector>()
+        );
+        assert_eq!(
+            Err(Error::NaN {
+                type_name: "sparsevec"
+            }),
+            "{1:nan}/3".parse::<SparseVector>()
+        );
+        assert_eq!(
+            Err(Error::OutOfRange {
+                type_name: "sparsevec",
+                value: "99999999999".to_string()
+            }),
+            "{99999999999:1}/3".parse::<SparseVector>()
+        );
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = SparseVector::from_dense(&[1.0, 0.0, 2.0, 0.0, 0.0]);
+        let b = SparseVector::from_dense(&[0.0, 3.0, 4.0, 0.0, 5.0]);
+        let (da, db) = (a.to_vec(), b.to_vec());
+        let l2: f32 = da.iter().zip(&db).map(|(x, y)| (x - y) * (x - y)).sum();
+        assert_eq!(f64::from(l2).sqrt(), a.l2_distance(&b).unwrap());
+        assert_eq!(f64::from(l2).sqrt(), b.l2_distance(&a).unwrap());
+        assert_eq!(8.0, a.inner_product(&b).unwrap());
+        assert_eq!(-8.0, a.max_inner_product(&b).unwrap());
+        assert_eq!(11.0, a.l1_distance(&b).unwrap());
+        let cosine = 1.0 - 8.0 / (5.0_f64 * 50.0).sqrt();
+        assert!((a.cosine_distance(&b).unwrap() - cosine).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_distances_empty() {
+        let a = SparseVector::from_dense(&[0.0, 0.0]);
+        let b = SparseVector::from_dense(&[3.0, 4.0]);
+        assert_eq!(5.0, a.l2_distance(&b).unwrap());
+        assert_eq!(0.0, a.inner_product(&b).unwrap());
+        assert!(a.cosine_distance(&b).unwrap().is_nan());
+    }
+
+    #[test]
+    fn test_distance_different_dimensions() {
+        let a = SparseVector::from_dense(&[1.0, 2.0]);
+        let b = SparseVector::from_dense(&[1.0, 2.0, 3.0]);
+        assert_eq!(
+            Err(Error::DifferentDimensions {
+                type_name: "sparsevec",
+                left: 2,
+                right: 3
+            }),
+            a.l1_distance(&b)
+        );
+    }
+
+    #[test]
+    fn test_norm() {
+        let vec = SparseVector::from_dense(&[3.0, 0.0, 4.0]);
+        assert_eq!(5.0, vec.norm());
+    }
+
+    #[test]
+    fn test_l2_normalize() {
+        let vec = SparseVector::from_dense(&[3.0, 0.0, 4.0]);
+        assert_eq!(vec![0.6, 0.0, 0.8], vec.l2_normalize().to_vec());
+
+        let vec = SparseVector::from_dense(&[1e20, 1e-30]);
+        let normalized = vec.l2_normalize();
+        assert_eq!(&[0], normalized.indices());
+        assert_eq!(&[1.0], normalized.values());
+
+        let vec = SparseVector::from_dense(&[0.0, 0.0]);
+        assert_eq!(vec, vec.l2_normalize());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let vec = SparseVector::from_dense(&[1.0, 0.0, 2.0]);
+        let json = serde_json::to_string(&vec).unwrap();
+        assert_eq!(r#"{"dim":3,"indices":[0,2],"values":[1.0,2.0]}"#, json);
+        assert_eq!(vec, serde_json::from_str(&json).unwrap());
+
+        let err =
+            serde_json::from_str::<SparseVector>(r#"{"dim":3,"indices":[2,0],"values":[1.0,2.0]}"#)
+                .unwrap_err();
+        assert_eq!("indexes must be in ascending order", err.to_string());
+    }
+
+    #[test]
+    fn test_l1_norm() {
+        let vec = SparseVector::from_dense(&[3.0, 0.0, -4.0]);
+        assert_eq!(7.0, vec.l1_norm());
+    }
+
+    #[test]
+    fn test_get() {
+        let vec = SparseVector::from_dense(&[1.0, 0.0, 2.0]);
+        assert_eq!(Some(1.0), vec.get(0));
+        assert_eq!(Some(0.0), vec.get(1));
+        assert_eq!(Some(2.0), vec.get(2));
+        assert_eq!(None, vec.get(3));
+    }
+
+    #[test]
+    fn test_top_k() {
+        let vec = SparseVector::from_dense(&[1.0, 0.0, -3.0, 2.0, 1.0]);
+        assert_eq!(vec![0.0, 0.0, -3.0, 2.0, 0.0], vec.top_k(2).to_vec());
+        assert_eq!(vec![1.0, 0.0, -3.0, 2.0, 0.0], vec.top_k(3).to_vec());
+        assert_eq!(vec, vec.top_k(10));
+        assert!(vec.top_k(0).indices().is_empty());
+
+        let vec = SparseVector::from_dense(&[1.0, f32::NAN, -3.0, 2.0, f32::INFINITY]);
+        assert_eq!(&[1, 4], vec.top_k(2).indices());
+        assert_eq!(&[1, 2, 4], vec.top_k(3).indices());
+    }
+
+    #[test]
+    fn test_add() {
+        let a = SparseVector::from_dense(&[1.0, 0.0, 2.0, 0.0]);
+        let b = SparseVector::from_dense(&[0.0, 3.0, -2.0, 0.0]);
+        let sum = (&a + &b).unwrap();
+        assert_eq!(&[0, 1], sum.indices());
+        assert_eq!(&[1.0, 3.0], sum.values());
+        assert_eq!(4, sum.dimensions());
+
+        let mut c = a.clone();
+        c += &b;
+        assert_eq!(sum, c);
+    }
+
+    #[test]
+    fn test_sub() {
+        let a = SparseVector::from_dense(&[1.0, 0.0, 2.0]);
+        let b = SparseVector::from_dense(&[0.0, 3.0, 2.0]);
+        assert_eq!(vec![1.0, -3.0, 0.0], (&a - &b).unwrap().to_vec());
+        assert!((&a - &a).unwrap().indices().is_empty());
+    }
+
+    #[test]
+    fn test_mul() {
+        let a = SparseVector::from_dense(&[1.0, 0.0, 2.0]);
+        let b = SparseVector::from_dense(&[3.0, 3.0, 0.0]);
+        let product = (&a * &b).unwrap();
+        assert_eq!(&[0], product.indices());
+        assert_eq!(&[3.0], product.values());
+
+        assert_eq!(vec![2.0, 0.0, 4.0], (&a * 2.0).unwrap().to_vec());
+        assert!((&a * 0.0).unwrap().indices().is_empty());
+    }
+
+    #[test]
+    fn test_ops_invalid() {
+        let a = SparseVector::from_dense(&[f32::MAX, 0.0]);
+        assert_eq!(Err(Error::Overflow), &a + &a);
+        assert_eq!(Err(Error::Overflow), &a * 2.0);
+        assert_eq!(
+            Err(Error::Underflow),
+            SparseVector::from_dense(&[1e-30]) * 1e-30
+        );
+        assert_eq!(
+            Err(Error::DifferentDimensions {
+                type_name: "sparsevec",
+                left: 2,
+                right: 3
+            }),
+            &a + &SparseVector::from_dense(&[1.0, 2.0, 3.0])
+        );
+
+        let a = SparseVector::try_new(20000, (0..16000).collect(), vec![1.0; 16000]).unwrap();
+        let b = SparseVector::try_new(20000, vec![16000], vec![1.0]).unwrap();
+        assert_eq!(Err(Error::TooManyNonZero { max: 16000 }), &a + &b);
+    }
+
+    #[test]
+    fn test_vector_conversions() {
+        let vec = Vector::from(vec![1.0, 0.0, 2.0]);
+        let sparse_vec = SparseVector::try_from(&vec).unwrap();
+        assert_eq!(&[0, 2], sparse_vec.indices());
+        assert_eq!(&[1.0, 2.0], sparse_vec.values());
+        assert_eq!(vec, Vector::try_from(sparse_vec).unwrap());
+
+        let vec = Vector::from(vec![1.0; 16001]);
+        assert_eq!(
+            Err(Error::TooManyNonZero { max: 16000 }),
+            SparseVector::try_from(vec)
+        );
+
+        let vec = Vector::from(vec![]);
+        assert_eq!(
+            Err(Error::TooFewDimensions {
+                type_name: "sparsevec"
+            }),
+            SparseVector::try_from(vec)
+        );
+    }
+
+    #[test]
+    fn test_vector_conversions_invalid() {
+        let vec = SparseVector::from_map(&HashMap::new(), 0);
+        assert_eq!(
+            Err(Error::TooFewDimensions {
+                type_name: "vector"
+            }),
+            Vector::try_from(vec)
+        );
+
+        let vec = SparseVector::from_map(&HashMap::new(), 16001);
+        assert_eq!(
+            Err(Error::TooManyDimensions {
+                type_name: "vector",
+                max: 16000
+            }),
+            Vector::try_from(vec)
+        );
+
+        let vec = SparseVector::from_map(&HashMap::from([(3, 1.0)]), 3);
+        assert_eq!(
+            Err(Error::IndexOutOfBounds { index: 3, dim: 3 }),
+            Vector::try_from(vec)
+        );
+    }
+
+    #[test]
+    fn test_try_new() {
+        let vec = SparseVector::try_new(6, vec![0, 2, 4], vec![1.0, 2.0, 3.0]).unwrap();
+        assert_eq!(vec![1.0, 0.0, 2.0, 0.0, 3.0, 0.0], vec.to_vec());
+
+        assert_eq!(
+            Err(Error::MismatchedLengths {
+                indices: 2,
+                values: 1
+            }),
+            SparseVector::try_new(6, vec![0, 2], vec![1.0])
+        );
+        assert_eq!(
+            Err(Error::TooFewDimensions {
+                type_name: "sparsevec"
+            }),
+            SparseVector::try_new(0, vec![], vec![])
+        );
+        assert_eq!(
+            Err(Error::TooManyDimensions {
+                type_name: "sparsevec",
+                max: 1000000000
+            }),
+            SparseVector::try_new(1000000001, vec![], vec![])
+        );
+        assert_eq!(
+            Err(Error::TooManyNonZero { max: 16000 }),
+            SparseVector::try_new(20000, (0..16001).collect(), vec![1.0; 16001])
+        );
+        assert_eq!(
+            Err(Error::IndexOutOfBounds { index: 6, dim: 6 }),
+            SparseVector::try_new(6, vec![6], vec![1.0])
+        );
+        assert_eq!(
+            Err(Error::IndexOutOfBounds { index: -1, dim: 6 }),
+            SparseVector::try_new(6, vec![-1], vec![1.0])
+        );
+        assert_eq!(
+            Err(Error::UnsortedIndices),
+            SparseVector::try_new(6, vec![2, 0], vec![1.0, 2.0])
+        );
+        assert_eq!(
+            Err(Error::DuplicateIndex { index: 2 }),
+            SparseVector::try_new(6, vec![2, 2], vec![1.0, 2.0])
+        );
+        assert_eq!(
+            Err(Error::NaN {
+                type_name: "sparsevec"
+            }),
+            SparseVector::try_new(6, vec![0], vec![f32::NAN])
+        );
+        assert_eq!(
+            Err(Error::ZeroValue { index: 0 }),
+            SparseVector::try_new(6, vec![0], vec![0.0])
+        );
+    }
 }
@@ -0,0 +1,287 @@
+use crate::limits;
+use crate::ops;
+use crate::sparsevec::{SPARSEVEC_MAX_DIM, SPARSEVEC_MAX_NNZ};
+use crate::text;
+use crate::{Error, SparseVector};
+
+/// How a sparse vector builder handles duplicate indices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    /// Adds the values.
+    Sum,
+    /// Keeps the largest value.
+    Max,
+    /// Keeps the last value pushed.
+    Last,
+    /// Returns an error.
+    Error,
+}
+
+impl Default for DuplicatePolicy {
+    fn default() -> Self {
+        DuplicatePolicy::Sum
+    }
+}
+
+/// A builder for sparse vectors.
+#[derive(Clone, Debug)]
+pub struct SparseVectorBuilder {
+    dim: i32,
+    elements: Vec<(u32, f32)>,
+    duplicates: DuplicatePolicy,
+    threshold: Option<f32>,
+    top_k: Option<usize>,
+}
+
+impl SparseVectorBuilder {
+    /// Creates a builder for a sparse vector with the given number of dimensions.
+    pub fn new(dim: i32) -> SparseVectorBuilder {
+        SparseVectorBuilder {
+            dim,
+            elements: Vec::new(),
+            duplicates: DuplicatePolicy::default(),
+            threshold: None,
+            top_k: None,
+        }
+    }
+
+    /// Sets how duplicate indices are handled (defaults to sum).
+    pub fn duplicates(&mut self, policy: DuplicatePolicy) -> &mut Self {
+        self.duplicates = policy;
+        self
+    }
+
+    /// Drops values whose absolute value is less than the threshold.
+    pub fn threshold(&mut self, threshold: f32) -> &mut Self {
+        self.threshold = Some(threshold);
+        self
+    }
+
+    /// Keeps only the `k` values with the largest absolute values.
+    pub fn top_k(&mut self, k: usize) -> &mut Self {
+        self.top_k = Some(k);
+        self
+    }
+
+    /// Adds an element.
+    pub fn push(&mut self, index: u32, value: f32) {
+        self.elements.push((index, value));
+    }
+
+    /// Builds the sparse vector, checking the same limits as the server.
+    ///
+    /// Duplicates are merged before thresholding and pruning.
+    pub fn build(self) -> Result<SparseVector, Error> {
+        let dim = self.dim;
+        limits::check_dims(
+            usize::try_from(dim).unwrap_or(0),
+            "sparsevec",
+            SPARSEVEC_MAX_DIM as usize,
+        )?;
+
+        let threshold = self.threshold.unwrap_or(0.0);
+        if threshold.is_nan() {
+            return Err(Error::NaN {
+                type_name: "sparsevec",
+            });
+        }
+
+        let mut elements = self.elements;
+        for (index, value) in &elements {
+            if i64::from(*index) >= i64::from(dim) {
+                return Err(Error::IndexOutOfBounds {
+                    index: i64::from(*index),
+                    dim,
+                });
+            }
+            text::check_element(*value, "sparsevec")?;
+        }
+
+        // stable to keep the order of duplicates
+        elements.sort_by_key(|v| v.0);
+
+        let mut merged: Vec<(u32, f32)> = Vec::with_capacity(elements.len());
+        for (index, value) in elements {
+            match merged.last_mut() {
+                Some(last) if last.0 == index => {
+                    last.1 = match self.duplicates {
+                        DuplicatePolicy::Sum => ops::check_result(last.1 + value)?,
+                        DuplicatePolicy::Max => last.1.max(value),
+                        DuplicatePolicy::Last => value,
+                        DuplicatePolicy::Error => {
+                            return Err(Error::DuplicateIndex {
+                                index: index as i32,
+                            })
+                        }
+                    };
+                }
+                _ => merged.push((index, value)),
+            }
+        }
+
+        merged.retain(|v| v.1 != 0.0 && v.1.abs() >= threshold);
+
+        let mut vec = SparseVector {
+            dim,
+            indices: merged.iter().map(|v| v.0 as i32).collect(),
+            values: merged.iter().map(|v| v.1).collect(),
+        };
+        if let Some(k) = self.top_k {
+            vec = vec.top_k(k);
+        }
+
+        if vec.indices.len() > SPARSEVEC_MAX_NNZ {
+            return Err(Error::TooManyNonZero {
+                max: SPARSEVEC_MAX_NNZ,
+            });
+        }
+
+        Ok(vec)
+    }
+}
+
+impl Extend<(u32, f32)> for SparseVectorBuilder {
+    fn extend<I: IntoIterator<Item = (u32, f32)>>(&mut self, iter: I) {
+        self.elements.extend(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DuplicatePolicy, Error, SparseVectorBuilder};
+
+    fn builder(dim: i32) -> SparseVectorBuilder {
+        let mut builder = SparseVectorBuilder::new(dim);
+        builder.extend([(4, 3.0), (0, 1.0), (2, 2.0), (0, 4.0)]);
+        builder
+    }
+
+    #[test]
+    fn test_build() {
+        let vec = builder(6).build().unwrap();
+        assert_eq!(6, vec.dimensions());
+        assert_eq!(&[0, 2, 4], vec.indices());
+        assert_eq!(&[5.0, 2.0, 3.0], vec.values());
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let mut b = builder(6);
+        b.duplicates(DuplicatePolicy::Max);
+        assert_eq!(&[4.0, 2.0, 3.0], b.build().unwrap().values());
+
+        let mut b = builder(6);
+        b.duplicates(DuplicatePolicy::Last);
+        assert_eq!(&[4.0, 2.0, 3.0], b.build().unwrap().values());
+
+        let mut b = SparseVectorBuilder::new(6);
+        b.duplicates(DuplicatePolicy::Last).push(1, 2.0);
+        b.push(1, 1.0);
+        assert_eq!(&[1.0], b.build().unwrap().values());
+
+        let mut b = builder(6);
+        b.duplicates(DuplicatePolicy::Error);
+        assert_eq!(Err(Error::DuplicateIndex { index: 0 }), b.build());
+    }
+
+    #[test]
+    fn test_duplicates_zero() {
+        let mut b = SparseVectorBuilder::new(3);
+        b.extend([(1, 1.0), (1, -1.0), (2, 0.0)]);
+        let vec = b.build().unwrap();
+        assert!(vec.indices().is_empty());
+    }
+
+    #[test]
+    fn test_duplicates_overflow() {
+        let mut b = SparseVectorBuilder::new(3);
+        b.extend([(1, f32::MAX), (1, f32::MAX)]);
+        assert_eq!(Err(Error::Overflow), b.build());
+    }
+
+    #[test]
+    fn test_threshold() {
+        let mut b = builder(6);
+        b.threshold(3.0);
+        let vec = b.build().unwrap();
+        assert_eq!(&[0, 4], vec.indices());
+        assert_eq!(&[5.0, 3.0], vec.values());
+
+        let mut b = builder(6);
+        b.threshold(f32::NAN);
+        assert_eq!(
+            Err(Error::NaN {
+                type_name: "sparsevec"
+            }),
+            b.build()
+        );
+    }
+
+    #[test]
+    fn test_top_k() {
+        let mut b = SparseVectorBuilder::new(6);
+        b.top_k(2).extend([(4, 3.0), (0, 1.0), (2, -4.0), (5, 1.0)]);
+        let vec = b.build().unwrap();
+        assert_eq!(&[2, 4], vec.indices());
+        assert_eq!(&[-4.0, 3.0], vec.values());
+
+        let mut b = SparseVectorBuilder::new(6);
+        b.top_k(1).extend([(5, 1.0), (0, 1.0)]);
+        assert_eq!(&[0], b.build().unwrap().indices());
+    }
+
+    #[test]
+    fn test_too_many_non_zero() {
+        let mut b = SparseVectorBuilder::new(20000);
+        b.extend((0..16001).map(|i| (i, 1.0)));
+        assert_eq!(Err(Error::TooManyNonZero { max: 16000 }), b.clone().build());
+        b.top_k(16000);
+        assert_eq!(16000, b.build().unwrap().indices().len());
+    }
+
+    #[test]
+    fn test_invalid() {
+        let mut b = SparseVectorBuilder::new(6);
+        b.push(6, 1.0);
+        assert_eq!(Err(Error::IndexOutOfBounds { index: 6, dim: 6 }), b.build());
+
+        let mut b = SparseVectorBuilder::new(6);
+        b.push(u32::MAX, 1.0);
+        assert_eq!(
+            Err(Error::IndexOutOfBounds {
+                index: u32::MAX.into(),
+                dim: 6
+            }),
+            b.build()
+        );
+
+        let mut b = SparseVectorBuilder::new(6);
+        b.push(0, f32::NAN);
+        assert_eq!(
+            Err(Error::NaN {
+                type_name: "sparsevec"
+            }),
+            b.build()
+        );
+
+        assert_eq!(
+            Err(Error::TooFewDimensions {
+                type_name: "sparsevec"
+            }),
+            SparseVectorBuilder::new(0).build()
+        );
+        assert_eq!(
+            Err(Error::TooFewDimensions {
+                type_name: "sparsevec"
+            }),
+            SparseVectorBuilder::new(-1).build()
+        );
+        assert_eq!(
+            Err(Error::TooManyDimensions {
+                type_name: "sparsevec",
+                max: 1_000_000_000
+            }),
+            SparseVectorBuilder::new(1_000_000_001).build()
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use sqlx::encode::IsNull;
 use sqlx::error::BoxDynError;
-use sqlx::postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef};
+use sqlx::postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef};
 use sqlx::{Decode, Encode, Postgres, Type};
 use std::convert::TryFrom;
 
@@ -10,6 +10,10 @@ impl Type<Postgres> for Bit {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::with_name("bit")
     }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::with_name("bit") || *ty == PgTypeInfo::with_name("varbit")
+    }
 }
 
 impl Encode<'_, Postgres> for Bit {
@@ -23,8 +27,13 @@ impl Encode<'_, Postgres> for Bit {
 
 impl Decode<'_, Postgres> for Bit {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
-        let buf = <&[u8] as Decode<Postgres>>::decode(value)?;
-        Bit::from_sql(buf)
+        match value.format() {
+            PgValueFormat::Binary => {
+                let buf = <&[u8] as Decode<Postgres>>::decode(value)?;
+                Ok(Bit::from_sql(buf)?)
+            }
+            PgValueFormat::Text => Ok(value.as_str()?.parse()?),
+        }
     }
 }
 
@@ -82,6 +91,13 @@ mod tests {
         let null_res: Option<Bit> = null_row.try_get("embedding").unwrap();
         assert!(null_res.is_none());
 
+        // ensures text format is supported
+        let raw_row = sqlx::raw_sql("SELECT embedding FROM sqlx_bit_items ORDER BY id LIMIT 1")
+            .fetch_one(&pool)
+            .await?;
+        let raw_res: Bit = raw_row.try_get("embedding").unwrap();
+        assert_eq!(vec, raw_res);
+
         // ensures binary format is correct
         let text_row =
             sqlx::query("SELECT embedding::text FROM sqlx_bit_items ORDER BY id LIMIT 1")
@@ -90,6 +106,16 @@ mod tests {
         let text_res: String = text_row.try_get("embedding").unwrap();
         assert_eq!("010100001", text_res);
 
+        // varbit
+        let row = sqlx::query("SELECT $1::varbit, B'101'::varbit")
+            .bind(Bit::new(&[true, true]))
+            .fetch_one(&pool)
+            .await?;
+        let res_vec: Bit = row.try_get(0).unwrap();
+        assert_eq!(Bit::new(&[true, true]), res_vec);
+        let res_vec: Bit = row.try_get(1).unwrap();
+        assert_eq!(Bit::new(&[true, false, true]), res_vec);
+
         sqlx::query("ALTER TABLE sqlx_bit_items ADD COLUMN factors bit(9)[]")
             .execute(&pool)
             .await?;
@@ -1,10 +1,10 @@
 use sqlx::encode::IsNull;
 use sqlx::error::BoxDynError;
-use sqlx::postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef};
+use sqlx::postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef};
 use sqlx::{Decode, Encode, Postgres, Type};
 use std::convert::TryFrom;
 
-use crate::HalfVector;
+use crate::{Error, FixedHalfVector, HalfVector, HalfVectorRef};
 
 impl Type<Postgres> for HalfVector {
     fn type_info() -> PgTypeInfo {
@@ -14,11 +14,41 @@ impl Type<Postgres> for HalfVector {
 
 impl Encode<'_, Postgres> for HalfVector {
     fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
-        let dim = self.0.len();
+        HalfVectorRef::from(self.as_slice()).encode_by_ref(buf)
+    }
+}
+
+impl Decode<'_, Postgres> for HalfVector {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => {
+                let buf = <&[u8] as Decode<Postgres>>::decode(value)?;
+                Ok(HalfVector::from_sql(buf)?)
+            }
+            PgValueFormat::Text => Ok(value.as_str()?.parse()?),
+        }
+    }
+}
+
+impl PgHasArrayType for HalfVector {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_halfvec")
+    }
+}
+
+impl Type<Postgres> for HalfVectorRef<'_> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("halfvec")
+    }
+}
+
+impl Encode<'_, Postgres> for HalfVectorRef<'_> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        let dim = self.len();
         buf.extend(&u16::try_from(dim)?.to_be_bytes());
         buf.extend(&0_u16.to_be_bytes());
 
-        for v in &self.0 {
+        for v in self.iter() {
             buf.extend(&v.to_be_bytes());
         }
 
@@ -26,14 +56,46 @@ impl Encode<'_, Postgres> for HalfVector {
     }
 }
 
-impl Decode<'_, Postgres> for HalfVector {
+impl<'r> Decode<'r, Postgres> for HalfVectorRef<'r> {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => {
+                let buf = <&[u8] as Decode<Postgres>>::decode(value)?;
+                Ok(HalfVectorRef::from_sql(buf)?)
+            }
+            PgValueFormat::Text => Err(Error::TextFormat {
+                type_name: "halfvec",
+            }
+            .into()),
+        }
+    }
+}
+
+impl<const N: usize> Type<Postgres> for FixedHalfVector<N> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("halfvec")
+    }
+}
+
+impl<const N: usize> Encode<'_, Postgres> for FixedHalfVector<N> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        HalfVectorRef::from(self.as_slice()).encode_by_ref(buf)
+    }
+}
+
+impl<const N: usize> Decode<'_, Postgres> for FixedHalfVector<N> {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
-        let buf = <&[u8] as Decode<Postgres>>::decode(value)?;
-        HalfVector::from_sql(buf)
+        match value.format() {
+            PgValueFormat::Binary => {
+                let buf = <&[u8] as Decode<Postgres>>::decode(value)?;
+                Ok(FixedHalfVector::from_sql(buf)?)
+            }
+            PgValueFormat::Text => Ok(value.as_str()?.parse::<HalfVector>()?.try_into()?),
+        }
     }
 }
 
-impl PgHasArrayType for HalfVector {
+impl<const N: usize> PgHasArrayType for FixedHalfVector<N> {
     fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::with_name("_halfvec")
     }
@@ -104,6 +166,13 @@ mod tests {
         let null_res: Option<HalfVector> = null_row.try_get("embedding").unwrap();
         assert!(null_res.is_none());
 
+        // ensures text format is supported
+        let raw_row = sqlx::raw_sql("SELECT embedding FROM sqlx_half_items ORDER BY id LIMIT 1")
+            .fetch_one(&pool)
+            .await?;
+        let raw_res: HalfVector = raw_row.try_get("embedding").unwrap();
+        assert_eq!(vec, raw_res);
+
         // ensures binary format is correct
         let text_row =
             sqlx::query("SELECT embedding::text FROM sqlx_half_items ORDER BY id LIMIT 1")
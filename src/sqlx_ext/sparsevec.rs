@@ -1,6 +1,6 @@
 use sqlx::encode::IsNull;
 use sqlx::error::BoxDynError;
-use sqlx::postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef};
+use sqlx::postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef};
 use sqlx::{Decode, Encode, Postgres, Type};
 use std::convert::TryFrom;
 
@@ -34,8 +34,13 @@ impl Encode<'_, Postgres> for SparseVector {
 
 impl Decode<'_, Postgres> for SparseVector {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
-        let buf = <&[u8] as Decode<Postgres>>::decode(value)?;
-        SparseVector::from_sql(buf)
+        match value.format() {
+            PgValueFormat::Binary => {
+                let buf = <&[u8] as Decode<Postgres>>::decode(value)?;
+                Ok(SparseVector::from_sql(buf)?)
+            }
+            PgValueFormat::Text => Ok(value.as_str()?.parse()?),
+        }
     }
 }
 
@@ -107,6 +112,13 @@ mod tests {
         let null_res: Option<SparseVector> = null_row.try_get("embedding").unwrap();
         assert!(null_res.is_none());
 
+        // ensures text format is supported
+        let raw_row = sqlx::raw_sql("SELECT embedding FROM sqlx_sparse_items ORDER BY id LIMIT 1")
+            .fetch_one(&pool)
+            .await?;
+        let raw_res: SparseVector = raw_row.try_get("embedding").unwrap();
+        assert_eq!(vec, raw_res);
+
         // ensures binary format is correct
         let text_row =
             sqlx::query("SELECT embedding::text FROM sqlx_sparse_items ORDER BY id LIMIT 1")
@@ -114,6 +126,7 @@ mod tests {
                 .await?;
         let text_res: String = text_row.try_get("embedding").unwrap();
         assert_eq!("{1:1,2:2,3:3}/3", text_res);
+        assert_eq!(vec, text_res.parse().unwrap());
 
         sqlx::query("ALTER TABLE sqlx_sparse_items ADD COLUMN factors sparsevec[]")
             .execute(&pool)
@@ -1,10 +1,10 @@
 use sqlx::encode::IsNull;
 use sqlx::error::BoxDynError;
-use sqlx::postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef};
+use sqlx::postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef};
 use sqlx::{Decode, Encode, Postgres, Type};
 use std::convert::TryFrom;
 
-use crate::Vector;
+use crate::{Error, FixedVector, Vector, VectorRef};
 
 impl Type<Postgres> for Vector {
     fn type_info() -> PgTypeInfo {
@@ -14,11 +14,41 @@ impl Type<Postgres> for Vector {
 
 impl Encode<'_, Postgres> for Vector {
     fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
-        let dim = self.0.len();
+        VectorRef::from(self.as_slice()).encode_by_ref(buf)
+    }
+}
+
+impl Decode<'_, Postgres> for Vector {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => {
+                let buf = <&[u8] as Decode<Postgres>>::decode(value)?;
+                Ok(Vector::from_sql(buf)?)
+            }
+            PgValueFormat::Text => Ok(value.as_str()?.parse()?),
+        }
+    }
+}
+
+impl PgHasArrayType for Vector {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_vector")
+    }
+}
+
+impl Type<Postgres> for VectorRef<'_> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("vector")
+    }
+}
+
+impl Encode<'_, Postgres> for VectorRef<'_> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        let dim = self.len();
         buf.extend(&u16::try_from(dim)?.to_be_bytes());
         buf.extend(&0_u16.to_be_bytes());
 
-        for v in &self.0 {
+        for v in self.iter() {
             buf.extend(&v.to_be_bytes());
         }
 
@@ -26,14 +56,46 @@ impl Encode<'_, Postgres> for Vector {
     }
 }
 
-impl Decode<'_, Postgres> for Vector {
+impl<'r> Decode<'r, Postgres> for VectorRef<'r> {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => {
+                let buf = <&[u8] as Decode<Postgres>>::decode(value)?;
+                Ok(VectorRef::from_sql(buf)?)
+            }
+            PgValueFormat::Text => Err(Error::TextFormat {
+                type_name: "vector",
+            }
+            .into()),
+        }
+    }
+}
+
+impl<const N: usize> Type<Postgres> for FixedVector<N> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("vector")
+    }
+}
+
+impl<const N: usize> Encode<'_, Postgres> for FixedVector<N> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        VectorRef::from(self.as_slice()).encode_by_ref(buf)
+    }
+}
+
+impl<const N: usize> Decode<'_, Postgres> for FixedVector<N> {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
-        let buf = <&[u8] as Decode<Postgres>>::decode(value)?;
-        Vector::from_sql(buf)
+        match value.format() {
+            PgValueFormat::Binary => {
+                let buf = <&[u8] as Decode<Postgres>>::decode(value)?;
+                Ok(FixedVector::from_sql(buf)?)
+            }
+            PgValueFormat::Text => Ok(value.as_str()?.parse::<Vector>()?.try_into()?),
+        }
     }
 }
 
-impl PgHasArrayType for Vector {
+impl<const N: usize> PgHasArrayType for FixedVector<N> {
     fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::with_name("_vector")
     }
@@ -41,7 +103,7 @@ impl PgHasArrayType for Vector {
 
 #[cfg(test)]
 mod tests {
-    use crate::Vector;
+    use crate::{Vector, VectorRef};
     use sqlx::postgres::PgPoolOptions;
     use sqlx::Row;
 
@@ -79,6 +141,14 @@ mod tests {
         assert_eq!(vec, res_vec);
         assert_eq!(vec![1.0, 2.0, 3.0], res_vec.to_vec());
 
+        let data = [1.0, 2.0, 3.0];
+        let row = sqlx::query("SELECT $1::vector AS embedding")
+            .bind(VectorRef::from(&data[..]))
+            .fetch_one(&pool)
+            .await?;
+        let res_ref: VectorRef = row.try_get("embedding").unwrap();
+        assert_eq!(VectorRef::from(&data[..]), res_ref);
+
         let empty_vec = Vector::from(vec![]);
         let empty_res = sqlx::query("INSERT INTO sqlx_items (embedding) VALUES ($1)")
             .bind(&empty_vec)
@@ -97,6 +167,13 @@ mod tests {
         let null_res: Option<Vector> = null_row.try_get("embedding").unwrap();
         assert!(null_res.is_none());
 
+        // ensures text format is supported
+        let raw_row = sqlx::raw_sql("SELECT embedding FROM sqlx_items ORDER BY id LIMIT 1")
+            .fetch_one(&pool)
+            .await?;
+        let raw_res: Vector = raw_row.try_get("embedding").unwrap();
+        assert_eq!(vec, raw_res);
+
         // ensures binary format is correct
         let text_row = sqlx::query("SELECT embedding::text FROM sqlx_items ORDER BY id LIMIT 1")
             .fetch_one(&pool)
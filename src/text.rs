@@ -0,0 +1,300 @@
+use std::fmt::{self, Write};
+
+use crate::Error;
+
+// matches vector_isspace in pgvector
+fn is_space(c: u8) -> bool {
+    matches!(c, b' ' | b'\t' | b'\n' | b'\r' | b'\x0b' | b'\x0c')
+}
+
+pub(crate) struct Scanner<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub(crate) fn new(input: &'a str) -> Scanner<'a> {
+        Scanner { input, pos: 0 }
+    }
+
+    fn bytes(&self) -> &'a [u8] {
+        &self.input.as_bytes()[self.pos..]
+    }
+
+    pub(crate) fn skip_space(&mut self) {
+        while self.bytes().first().map_or(false, |c| is_space(*c)) {
+            self.pos += 1;
+        }
+    }
+
+    pub(crate) fn peek(&self) -> Option<u8> {
+        self.bytes().first().copied()
+    }
+
+    pub(crate) fn eat(&mut self, c: u8) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.pos == self.input.len()
+    }
+
+    #[cfg(feature = "halfvec")]
+    pub(crate) fn position(&self) -> usize {
+        self.pos
+    }
+
+    #[cfg(feature = "halfvec")]
+    pub(crate) fn since(&self, start: usize) -> &'a str {
+        &self.input[start..self.pos]
+    }
+
+    fn digits(&self, start: usize) -> usize {
+        self.input.as_bytes()[start..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count()
+    }
+
+    fn sign(&self, start: usize) -> usize {
+        match self.input.as_bytes().get(start) {
+            Some(b'+') | Some(b'-') => 1,
+            _ => 0,
+        }
+    }
+
+    // like strtol, returning the token and leaving range checks to the caller
+    pub(crate) fn int(&mut self) -> Option<&'a str> {
+        let start = self.pos;
+        let sign = self.sign(start);
+        let digits = self.digits(start + sign);
+        if digits == 0 {
+            return None;
+        }
+        self.pos = start + sign + digits;
+        Some(&self.input[start..self.pos])
+    }
+
+    // like strtof, but without hexadecimal input
+    pub(crate) fn float(&mut self, type_name: &'static str) -> Result<f32, Error> {
+        let start = self.pos;
+        let mut end = start + self.sign(start);
+
+        let rest = &self.input.as_bytes()[end..];
+        for special in ["infinity", "inf", "nan"] {
+            if rest.len() >= special.len()
+                && rest[..special.len()].eq_ignore_ascii_case(special.as_bytes())
+            {
+                self.pos = end + special.len();
+                return Ok(self.input[start..self.pos].parse().unwrap());
+            }
+        }
+
+        let int_digits = self.digits(end);
+        end += int_digits;
+        let mut frac_digits = 0;
+        if self.input.as_bytes().get(end) == Some(&b'.') {
+            frac_digits = self.digits(end + 1);
+            if int_digits > 0 || frac_digits > 0 {
+                end += 1 + frac_digits;
+            }
+        }
+        if int_digits == 0 && frac_digits == 0 {
+            return Err(Error::InvalidSyntax { type_name });
+        }
+
+        if matches!(self.input.as_bytes().get(end), Some(b'e') | Some(b'E')) {
+            let sign = self.sign(end + 1);
+            let exp_digits = self.digits(end + 1 + sign);
+            if exp_digits > 0 {
+                end += 1 + sign + exp_digits;
+            }
+        }
+
+        self.pos = end;
+        let token = &self.input[start..end];
+        let value: f32 = token.parse().unwrap();
+        if value.is_infinite() {
+            return Err(Error::OutOfRange {
+                type_name,
+                value: token.to_string(),
+            });
+        }
+        Ok(value)
+    }
+}
+
+pub(crate) fn check_element(value: f32, type_name: &'static str) -> Result<(), Error> {
+    if value.is_nan() {
+        return Err(Error::NaN { type_name });
+    }
+    if value.is_infinite() {
+        return Err(Error::Infinite { type_name });
+    }
+    Ok(())
+}
+
+// parses the [1,2,3] format shared by vector and halfvec
+pub(crate) fn parse_array<T, F>(
+    s: &str,
+    type_name: &'static str,
+    max_dim: usize,
+    mut element: F,
+) -> Result<Vec<T>, Error>
+where
+    F: FnMut(&mut Scanner<'_>) -> Result<T, Error>,
+{
+    let mut scanner = Scanner::new(s);
+    scanner.skip_space();
+    if !scanner.eat(b'[') {
+        return Err(Error::InvalidSyntax { type_name });
+    }
+
+    scanner.skip_space();
+    if scanner.peek() == Some(b']') {
+        return Err(Error::TooFewDimensions { type_name });
+    }
+
+    let mut vec = Vec::new();
+    loop {
+        if vec.len() == max_dim {
+            return Err(Error::TooManyDimensions {
+                type_name,
+                max: max_dim,
+            });
+        }
+
+        scanner.skip_space();
+        vec.push(element(&mut scanner)?);
+        scanner.skip_space();
+
+        if scanner.eat(b',') {
+            continue;
+        }
+        if scanner.eat(b']') {
+            break;
+        }
+        return Err(Error::InvalidSyntax { type_name });
+    }
+
+    scanner.skip_space();
+    if !scanner.is_done() {
+        return Err(Error::InvalidSyntax { type_name });
+    }
+
+    Ok(vec)
+}
+
+struct Buffer {
+    data: [u8; 32],
+    len: usize,
+}
+
+impl Write for Buffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > self.data.len() {
+            return Err(fmt::Error);
+        }
+        self.data[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+// writes the shortest representation that round-trips, using the same
+// notation as float_to_shortest_decimal_bufn in Postgres
+pub(crate) fn write_float<W: Write>(w: &mut W, value: f32) -> fmt::Result {
+    if !value.is_finite() {
+        return match value {
+            v if v.is_nan() => w.write_str("NaN"),
+            v if v > 0.0 => w.write_str("Infinity"),
+            _ => w.write_str("-Infinity"),
+        };
+    }
+
+    let mut buf = Buffer {
+        data: [0; 32],
+        len: 0,
+    };
+    write!(buf, "{:e}", value)?;
+    let s = std::str::from_utf8(&buf.data[..buf.len]).unwrap();
+
+    let (sign, s) = match s.strip_prefix('-') {
+        Some(s) => ("-", s),
+        None => ("", s),
+    };
+    let (mantissa, exp) = s.split_once('e').unwrap();
+    let exp: i32 = exp.parse().unwrap();
+    let (first, rest) = match mantissa.split_once('.') {
+        Some((first, rest)) => (first, rest),
+        None => (mantissa, ""),
+    };
+
+    w.write_str(sign)?;
+    if (-4..6).contains(&exp) {
+        if exp < 0 {
+            w.write_str("0.")?;
+            for _ in 0..(-exp - 1) {
+                w.write_char('0')?;
+            }
+            w.write_str(first)?;
+            w.write_str(rest)?;
+        } else {
+            let exp = exp as usize;
+            w.write_str(first)?;
+            if rest.len() > exp {
+                w.write_str(&rest[..exp])?;
+                w.write_char('.')?;
+                w.write_str(&rest[exp..])?;
+            } else {
+                w.write_str(rest)?;
+                for _ in 0..(exp - rest.len()) {
+                    w.write_char('0')?;
+                }
+            }
+        }
+    } else {
+        w.write_str(first)?;
+        if !rest.is_empty() {
+            w.write_char('.')?;
+            w.write_str(rest)?;
+        }
+        let exp_sign = if exp < 0 { '-' } else { '+' };
+        write!(w, "e{}{:02}", exp_sign, exp.abs())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::write_float;
+
+    fn format(value: f32) -> String {
+        let mut s = String::new();
+        write_float(&mut s, value).unwrap();
+        s
+    }
+
+    #[test]
+    fn test_write_float() {
+        assert_eq!("0", format(0.0));
+        assert_eq!("-0", format(-0.0));
+        assert_eq!("1", format(1.0));
+        assert_eq!("-1.5", format(-1.5));
+        assert_eq!("0.1", format(0.1));
+        assert_eq!("123456", format(123456.0));
+        assert_eq!("1e+06", format(1000000.0));
+        assert_eq!("1.234567e+06", format(1234567.0));
+        assert_eq!("0.0001", format(0.0001));
+        assert_eq!("1e-05", format(0.00001));
+        assert_eq!("1.5e-07", format(0.00000015));
+        assert_eq!("3.4028235e+38", format(f32::MAX));
+        assert_eq!("1e-45", format(f32::from_bits(1)));
+    }
+}
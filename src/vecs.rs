@@ -0,0 +1,524 @@
+//! Readers and writers for the `.fvecs`, `.ivecs`, and `.bvecs` formats used by ANN benchmark datasets.
+//!
+//! Each record is a little-endian `i32` number of dimensions followed by that many elements (`f32`, `i32`, or `u8`). Records are read one at a time, so files do not need to fit in memory.
+
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::limits;
+use crate::vector::VECTOR_MAX_DIM;
+use crate::{Error, Vector};
+
+#[cfg(feature = "halfvec")]
+use crate::halfvec::HALFVEC_MAX_DIM;
+
+#[cfg(feature = "halfvec")]
+use crate::HalfVector;
+
+#[cfg(feature = "halfvec")]
+use half::f16;
+
+struct RecordReader<R> {
+    reader: R,
+    buf: Vec<u8>,
+    done: bool,
+}
+
+impl<R: Read> RecordReader<R> {
+    fn new(reader: R) -> RecordReader<R> {
+        RecordReader {
+            reader,
+            buf: Vec::new(),
+            done: false,
+        }
+    }
+
+    // returns the body of the next record, or None at the end of the file
+    fn read_record(
+        &mut self,
+        size: usize,
+        check: impl FnOnce(usize) -> Result<(), Error>,
+    ) -> io::Result<Option<&[u8]>> {
+        if self.done {
+            return Ok(None);
+        }
+        let res = self.read_len(size, check).and_then(|len| match len {
+            Some(len) => self.read_body(len).map(|_| true),
+            None => Ok(false),
+        });
+        match res {
+            Ok(true) => Ok(Some(&self.buf)),
+            Ok(false) => Ok(None),
+            Err(e) => {
+                // stop after an error since the position is unknown
+                self.done = true;
+                Err(e)
+            }
+        }
+    }
+
+    fn read_len(
+        &mut self,
+        size: usize,
+        check: impl FnOnce(usize) -> Result<(), Error>,
+    ) -> io::Result<Option<usize>> {
+        let dim = match self.read_dim()? {
+            Some(dim) => dim,
+            None => return Ok(None),
+        };
+        let dim = usize::try_from(dim)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "negative record length"))?;
+        check(dim).map_err(invalid_data)?;
+        dim.checked_mul(size)
+            .map(Some)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "record length out of range"))
+    }
+
+    // returns None at the end of the file
+    fn read_dim(&mut self) -> io::Result<Option<i32>> {
+        let mut header = [0; 4];
+        let mut n = 0;
+        while n < header.len() {
+            match self.reader.read(&mut header[n..]) {
+                Ok(0) if n == 0 => return Ok(None),
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(m) => n += m,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(Some(i32::from_le_bytes(header)))
+    }
+
+    fn read_body(&mut self, len: usize) -> io::Result<()> {
+        // grow the buffer as data is read rather than trusting the header
+        self.buf.clear();
+        (&mut self.reader)
+            .take(len as u64)
+            .read_to_end(&mut self.buf)?;
+        if self.buf.len() < len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(())
+    }
+
+    fn read_vector_body(
+        &mut self,
+        type_name: &'static str,
+        max_dim: usize,
+        size: usize,
+    ) -> io::Result<Option<&[u8]>> {
+        self.read_record(size, |dim| limits::check_dims(dim, type_name, max_dim))
+    }
+}
+
+fn invalid_data(e: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+fn write_dim<W: Write>(writer: &mut W, len: usize) -> io::Result<()> {
+    let dim = i32::try_from(len).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "record has too many dimensions",
+        )
+    })?;
+    writer.write_all(&dim.to_le_bytes())
+}
+
+/// A reader for `.fvecs` files.
+pub struct FvecsReader<R> {
+    inner: RecordReader<R>,
+}
+
+impl FvecsReader<BufReader<File>> {
+    /// Opens a file with a buffered reader.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<R: Read> FvecsReader<R> {
+    /// Creates a reader (use a buffered reader for performance).
+    pub fn new(reader: R) -> FvecsReader<R> {
+        FvecsReader {
+            inner: RecordReader::new(reader),
+        }
+    }
+
+    /// Reads the next vector, checking the same limits as the server.
+    pub fn read_vector(&mut self) -> io::Result<Option<Vector>> {
+        let buf = match self.inner.read_vector_body("vector", VECTOR_MAX_DIM, 4)? {
+            Some(buf) => buf,
+            None => return Ok(None),
+        };
+        let vec = buf
+            .chunks_exact(4)
+            .map(|v| f32::from_le_bytes(v.try_into().unwrap()))
+            .collect();
+        Vector::try_new(vec).map(Some).map_err(invalid_data)
+    }
+}
+
+impl<R: Read> Iterator for FvecsReader<R> {
+    type Item = io::Result<Vector>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_vector().transpose()
+    }
+}
+
+/// A reader for `.bvecs` files.
+pub struct BvecsReader<R> {
+    inner: RecordReader<R>,
+}
+
+impl BvecsReader<BufReader<File>> {
+    /// Opens a file with a buffered reader.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<R: Read> BvecsReader<R> {
+    /// Creates a reader (use a buffered reader for performance).
+    pub fn new(reader: R) -> BvecsReader<R> {
+        BvecsReader {
+            inner: RecordReader::new(reader),
+        }
+    }
+
+    /// Reads the next vector.
+    pub fn read_vector(&mut self) -> io::Result<Option<Vector>> {
+        let buf = self.inner.read_vector_body("vector", VECTOR_MAX_DIM, 1)?;
+        Ok(buf.map(|v| Vector::from(v.iter().map(|&x| f32::from(x)).collect::<Vec<f32>>())))
+    }
+
+    /// Reads the next vector as a half vector.
+    #[cfg(feature = "halfvec")]
+    pub fn read_half_vector(&mut self) -> io::Result<Option<HalfVector>> {
+        let buf = self.inner.read_vector_body("halfvec", HALFVEC_MAX_DIM, 1)?;
+        Ok(buf.map(|v| HalfVector::from(v.iter().map(|&x| f16::from(x)).collect::<Vec<f16>>())))
+    }
+}
+
+impl<R: Read> Iterator for BvecsReader<R> {
+    type Item = io::Result<Vector>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_vector().transpose()
+    }
+}
+
+/// A reader for `.ivecs` files, like ground truth neighbor lists.
+pub struct IvecsReader<R> {
+    inner: RecordReader<R>,
+}
+
+impl IvecsReader<BufReader<File>> {
+    /// Opens a file with a buffered reader.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<R: Read> IvecsReader<R> {
+    /// Creates a reader (use a buffered reader for performance).
+    pub fn new(reader: R) -> IvecsReader<R> {
+        IvecsReader {
+            inner: RecordReader::new(reader),
+        }
+    }
+
+    /// Reads the next record.
+    pub fn read_record(&mut self) -> io::Result<Option<Vec<i32>>> {
+        let buf = match self.inner.read_record(4, |_| Ok(()))? {
+            Some(buf) => buf,
+            None => return Ok(None),
+        };
+        Ok(Some(
+            buf.chunks_exact(4)
+                .map(|v| i32::from_le_bytes(v.try_into().unwrap()))
+                .collect(),
+        ))
+    }
+}
+
+impl<R: Read> Iterator for IvecsReader<R> {
+    type Item = io::Result<Vec<i32>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+/// A writer for `.fvecs` files.
+pub struct FvecsWriter<W: Write> {
+    writer: W,
+}
+
+impl FvecsWriter<BufWriter<File>> {
+    /// Creates a file with a buffered writer.
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> FvecsWriter<W> {
+    /// Creates a writer (use a buffered writer for performance).
+    pub fn new(writer: W) -> FvecsWriter<W> {
+        FvecsWriter { writer }
+    }
+
+    /// Writes a vector.
+    pub fn write(&mut self, vec: &[f32]) -> io::Result<()> {
+        write_dim(&mut self.writer, vec.len())?;
+        for v in vec {
+            self.writer.write_all(&v.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Flushes the writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// A writer for `.bvecs` files.
+pub struct BvecsWriter<W: Write> {
+    writer: W,
+}
+
+impl BvecsWriter<BufWriter<File>> {
+    /// Creates a file with a buffered writer.
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> BvecsWriter<W> {
+    /// Creates a writer (use a buffered writer for performance).
+    pub fn new(writer: W) -> BvecsWriter<W> {
+        BvecsWriter { writer }
+    }
+
+    /// Writes a vector.
+    pub fn write(&mut self, vec: &[u8]) -> io::Result<()> {
+        write_dim(&mut self.writer, vec.len())?;
+        self.writer.write_all(vec)
+    }
+
+    /// Flushes the writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// A writer for `.ivecs` files, like result sets of neighbor ids.
+pub struct IvecsWriter<W: Write> {
+    writer: W,
+}
+
+impl IvecsWriter<BufWriter<File>> {
+    /// Creates a file with a buffered writer.
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> IvecsWriter<W> {
+    /// Creates a writer (use a buffered writer for performance).
+    pub fn new(writer: W) -> IvecsWriter<W> {
+        IvecsWriter { writer }
+    }
+
+    /// Writes a record.
+    pub fn write(&mut self, record: &[i32]) -> io::Result<()> {
+        write_dim(&mut self.writer, record.len())?;
+        for v in record {
+            self.writer.write_all(&v.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Flushes the writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::vecs::{
+        BvecsReader, BvecsWriter, FvecsReader, FvecsWriter, IvecsReader, IvecsWriter,
+    };
+    use crate::{Error, Vector};
+    use std::io::{self, Cursor};
+
+    #[test]
+    fn test_fvecs() {
+        let mut writer = FvecsWriter::new(Vec::new());
+        writer.write(&[1.0, 2.0, 3.0]).unwrap();
+        writer.write(&[4.0, 5.0]).unwrap();
+        let buf = writer.into_inner();
+        assert_eq!(28, buf.len());
+        assert_eq!(&[3, 0, 0, 0], &buf[..4]);
+
+        let vecs: Vec<Vector> = FvecsReader::new(Cursor::new(buf))
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(
+            vec![
+                Vector::from(vec![1.0, 2.0, 3.0]),
+                Vector::from(vec![4.0, 5.0])
+            ],
+            vecs
+        );
+    }
+
+    #[test]
+    fn test_fvecs_file() {
+        let path = std::env::temp_dir().join(format!("pgvector-{}.fvecs", std::process::id()));
+        let mut writer = FvecsWriter::create(&path).unwrap();
+        writer.write(&[1.0, 2.0, 3.0]).unwrap();
+        writer.flush().unwrap();
+        drop(writer);
+
+        let mut reader = FvecsReader::open(&path).unwrap();
+        assert_eq!(
+            Some(Vector::from(vec![1.0, 2.0, 3.0])),
+            reader.read_vector().unwrap()
+        );
+        assert_eq!(None, reader.read_vector().unwrap());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_fvecs_invalid() {
+        let mut reader = FvecsReader::new(Cursor::new(vec![0, 0, 0, 0]));
+        let err = reader.read_vector().unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+        assert_eq!(
+            Some(&Error::TooFewDimensions {
+                type_name: "vector"
+            }),
+            err.get_ref().and_then(|e| e.downcast_ref::<Error>())
+        );
+
+        let mut reader = FvecsReader::new(Cursor::new((-1i32).to_le_bytes().to_vec()));
+        let err = reader.read_vector().unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+        assert_eq!("negative record length", err.to_string());
+
+        // the body is still read, so later records can be read
+        let mut writer = FvecsWriter::new(Vec::new());
+        writer.write(&[f32::NAN]).unwrap();
+        writer.write(&[1.0]).unwrap();
+        let mut reader = FvecsReader::new(Cursor::new(writer.into_inner()));
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+        assert_eq!(
+            Some(&Error::NaN {
+                type_name: "vector"
+            }),
+            err.get_ref().and_then(|e| e.downcast_ref::<Error>())
+        );
+        assert_eq!(Vector::from(vec![1.0]), reader.next().unwrap().unwrap());
+    }
+
+    #[test]
+    fn test_fvecs_too_many_dimensions() {
+        let mut buf = 16001i32.to_le_bytes().to_vec();
+        buf.resize(4 + 16001 * 4, 0);
+        buf.extend_from_slice(&1i32.to_le_bytes());
+        buf.extend_from_slice(&1.0f32.to_le_bytes());
+        let mut reader = FvecsReader::new(Cursor::new(buf));
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+        assert_eq!(
+            Some(&Error::TooManyDimensions {
+                type_name: "vector",
+                max: 16000
+            }),
+            err.get_ref().and_then(|e| e.downcast_ref::<Error>())
+        );
+        // stops since the body is not read
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_truncated() {
+        let mut reader = FvecsReader::new(Cursor::new(vec![3, 0]));
+        assert_eq!(
+            io::ErrorKind::UnexpectedEof,
+            reader.read_vector().unwrap_err().kind()
+        );
+
+        let mut buf = 3i32.to_le_bytes().to_vec();
+        buf.extend_from_slice(&1.0f32.to_le_bytes());
+        let mut reader = FvecsReader::new(Cursor::new(buf));
+        assert_eq!(
+            io::ErrorKind::UnexpectedEof,
+            reader.read_vector().unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    fn test_bvecs() {
+        let mut writer = BvecsWriter::new(Vec::new());
+        writer.write(&[0, 1, 255]).unwrap();
+        let buf = writer.into_inner();
+
+        let mut reader = BvecsReader::new(Cursor::new(buf.clone()));
+        assert_eq!(
+            Some(Vector::from(vec![0.0, 1.0, 255.0])),
+            reader.read_vector().unwrap()
+        );
+        assert!(reader.next().is_none());
+
+        #[cfg(feature = "halfvec")]
+        {
+            let mut reader = BvecsReader::new(Cursor::new(buf));
+            assert_eq!(
+                Some(crate::HalfVector::from_f32_slice(&[0.0, 1.0, 255.0])),
+                reader.read_half_vector().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_ivecs() {
+        let mut writer = IvecsWriter::new(Vec::new());
+        writer.write(&[3, 1, 2]).unwrap();
+        writer.write(&[]).unwrap();
+        let buf = writer.into_inner();
+
+        let records: Vec<Vec<i32>> = IvecsReader::new(Cursor::new(buf))
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(vec![vec![3, 1, 2], vec![]], records);
+
+        let mut reader = IvecsReader::new(Cursor::new((-1i32).to_le_bytes().to_vec()));
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+        assert_eq!("negative record length", err.to_string());
+        assert!(reader.next().is_none());
+    }
+}
@@ -1,6 +1,3 @@
-#[cfg(any(feature = "postgres", feature = "sqlx", feature = "diesel"))]
-use std::convert::TryInto;
-
 #[cfg(feature = "diesel")]
 use crate::diesel_ext::vector::VectorType;
 
@@ -10,6 +7,20 @@ use diesel::{deserialize::FromSqlRow, expression::AsExpression};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use std::fmt;
+use std::str::FromStr;
+
+use crate::distance;
+use crate::limits;
+use crate::ops;
+use crate::text;
+use crate::{Bit, Error};
+
+#[cfg(any(feature = "postgres", feature = "sqlx", feature = "diesel"))]
+use crate::VectorRef;
+
+pub(crate) const VECTOR_MAX_DIM: usize = 16000;
+
 /// A vector.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "diesel", derive(FromSqlRow, AsExpression))]
@@ -30,6 +41,15 @@ impl From<Vector> for Vec<f32> {
 }
 
 impl Vector {
+    /// Creates a vector, checking the same limits as the server.
+    pub fn try_new(vec: Vec<f32>) -> Result<Vector, Error> {
+        limits::check_dims(vec.len(), "vector", VECTOR_MAX_DIM)?;
+        for v in &vec {
+            text::check_element(*v, "vector")?;
+        }
+        Ok(Vector(vec))
+    }
+
     /// Returns a copy of the vector as a `Vec<f32>`.
     pub fn to_vec(&self) -> Vec<f32> {
         self.0.clone()
@@ -40,35 +60,175 @@ impl Vector {
         self.0.as_slice()
     }
 
-    #[cfg(any(feature = "postgres", feature = "sqlx", feature = "diesel"))]
-    pub(crate) fn from_sql(buf: &[u8]) -> Result<Vector, Box<dyn std::error::Error + Sync + Send>> {
-        if buf.len() < 4 {
-            return Err("invalid length".into());
+    /// Returns the number of dimensions.
+    pub fn dimensions(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns the Euclidean norm, like the `vector_norm` function.
+    pub fn norm(&self) -> f64 {
+        self.0
+            .iter()
+            .map(|v| {
+                let v = f64::from(*v);
+                v * v
+            })
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// Returns the vector normalized to unit length, like the `l2_normalize` function.
+    ///
+    /// A zero vector stays zero.
+    pub fn l2_normalize(&self) -> Vector {
+        let norm = self.norm();
+        if norm > 0.0 {
+            Vector(
+                self.0
+                    .iter()
+                    .map(|v| (f64::from(*v) / norm) as f32)
+                    .collect(),
+            )
+        } else {
+            Vector(vec![0.0; self.0.len()])
         }
+    }
+
+    /// Returns `count` elements starting at the 1-based position `start`, like the `subvector` function.
+    pub fn subvector(&self, start: i32, count: i32) -> Result<Vector, Error> {
+        let range = ops::subvector_range(self.0.len(), start, count, "vector")?;
+        Ok(Vector(self.0[range].to_vec()))
+    }
+
+    /// Returns a bit string with the sign of each element, like the `binary_quantize` function.
+    pub fn binary_quantize(&self) -> Bit {
+        Bit::from_bits(self.0.iter().map(|v| *v > 0.0))
+    }
+
+    /// Returns the L2 distance to another vector, like the `<->` operator.
+    pub fn l2_distance(&self, other: &Vector) -> Result<f64, Error> {
+        self.check_dims(other)?;
+        let distance = distance::l2_squared_distance(&self.0, &other.0);
+        Ok(f64::from(distance).sqrt())
+    }
+
+    /// Returns the inner product with another vector.
+    pub fn inner_product(&self, other: &Vector) -> Result<f64, Error> {
+        self.check_dims(other)?;
+        Ok(f64::from(distance::inner_product(&self.0, &other.0)))
+    }
+
+    /// Returns the negative inner product with another vector, like the `<#>` operator.
+    pub fn max_inner_product(&self, other: &Vector) -> Result<f64, Error> {
+        self.inner_product(other).map(|v| -v)
+    }
+
+    /// Returns the cosine distance to another vector, like the `<=>` operator.
+    pub fn cosine_distance(&self, other: &Vector) -> Result<f64, Error> {
+        self.check_dims(other)?;
+        let similarity = distance::cosine_similarity(&self.0, &other.0);
+        Ok(distance::cosine_distance(similarity))
+    }
 
-        let dim = u16::from_be_bytes(buf[0..2].try_into()?).into();
-        let unused = u16::from_be_bytes(buf[2..4].try_into()?);
-        if unused != 0 {
-            return Err("expected unused to be 0".into());
+    /// Returns the L1 distance to another vector, like the `<+>` operator.
+    pub fn l1_distance(&self, other: &Vector) -> Result<f64, Error> {
+        self.check_dims(other)?;
+        Ok(f64::from(distance::l1_distance(&self.0, &other.0)))
+    }
+
+    /// Concatenates with another vector, like the `||` operator.
+    pub fn concat(&self, other: &Vector) -> Result<Vector, Error> {
+        if self.0.len() + other.0.len() > VECTOR_MAX_DIM {
+            return Err(Error::TooManyDimensions {
+                type_name: "vector",
+                max: VECTOR_MAX_DIM,
+            });
         }
+        Ok(Vector([self.as_slice(), other.as_slice()].concat()))
+    }
+
+    fn try_add(&self, other: &Vector) -> Result<Vector, Error> {
+        self.zip_with(other, |a, b| ops::check_result(a + b))
+    }
+
+    fn try_sub(&self, other: &Vector) -> Result<Vector, Error> {
+        self.zip_with(other, |a, b| ops::check_result(a - b))
+    }
 
-        if (buf.len() - 4) / 4 != dim || buf.len() % 4 != 0 {
-            return Err("invalid length".into());
+    fn try_mul(&self, other: &Vector) -> Result<Vector, Error> {
+        self.zip_with(other, |a, b| ops::check_product(a * b, a, b))
+    }
+
+    fn try_scale(&self, scalar: f32) -> Result<Vector, Error> {
+        self.0
+            .iter()
+            .map(|&a| ops::check_product(a * scalar, a, scalar))
+            .collect::<Result<_, _>>()
+            .map(Vector)
+    }
+
+    fn zip_with<F: Fn(f32, f32) -> Result<f32, Error>>(
+        &self,
+        other: &Vector,
+        f: F,
+    ) -> Result<Vector, Error> {
+        self.check_dims(other)?;
+        self.0
+            .iter()
+            .zip(&other.0)
+            .map(|(&a, &b)| f(a, b))
+            .collect::<Result<_, _>>()
+            .map(Vector)
+    }
+
+    fn check_dims(&self, other: &Vector) -> Result<(), Error> {
+        if self.0.len() != other.0.len() {
+            return Err(Error::DifferentDimensions {
+                type_name: "vector",
+                left: self.0.len(),
+                right: other.0.len(),
+            });
         }
+        Ok(())
+    }
 
-        let mut vec = Vec::with_capacity(dim);
-        for i in 0..dim {
-            let s = 4 + 4 * i;
-            vec.push(f32::from_be_bytes(buf[s..s + 4].try_into()?));
+    #[cfg(any(feature = "postgres", feature = "sqlx", feature = "diesel"))]
+    pub(crate) fn from_sql(buf: &[u8]) -> Result<Vector, Error> {
+        Ok(VectorRef::from_sql(buf)?.to_vector())
+    }
+}
+
+ops::impl_ops!(Vector, f32);
+
+impl fmt::Display for Vector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        for (i, v) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            text::write_float(f, *v)?;
         }
+        f.write_str("]")
+    }
+}
+
+impl FromStr for Vector {
+    type Err = Error;
 
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let vec = text::parse_array(s, "vector", VECTOR_MAX_DIM, |scanner| {
+            let v = scanner.float("vector")?;
+            text::check_element(v, "vector")?;
+            Ok(v)
+        })?;
         Ok(Vector(vec))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::Vector;
+    use crate::{Error, Vector};
 
     #[test]
     fn test_into() {
@@ -89,6 +249,79 @@ mod tests {
         assert_eq!(vec.as_slice(), &[1.0, 2.0, 3.0]);
     }
 
+    #[test]
+    fn test_display() {
+        let vec = Vector::from(vec![1.0, -2.5, 0.1, 1e20]);
+        assert_eq!("[1,-2.5,0.1,1e+20]", vec.to_string());
+    }
+
+    #[test]
+    fn test_from_str() {
+        let vec: Vector = "[1,2,3]".parse().unwrap();
+        assert_eq!(Vector::from(vec![1.0, 2.0, 3.0]), vec);
+
+        let vec: Vector = " [ 1.5 , -2e3,\t.5e-1 ] ".parse().unwrap();
+        assert_eq!(Vector::from(vec![1.5, -2000.0, 0.05]), vec);
+    }
+
+    #[test]
+    fn test_from_str_round_trip() {
+        let vec = Vector::from(vec![0.1, 1.0 / 3.0, f32::MAX, f32::MIN_POSITIVE, -0.0]);
+        assert_eq!(vec, vec.to_string().parse().unwrap());
+    }
+
+    #[test]
+    fn test_from_str_invalid() {
+        let syntax = Error::InvalidSyntax {
+            type_name: "vector",
+        };
+        assert_eq!(Err(syntax.clone()), "1,2,3".parse::<Vector>());
+        assert_eq!(Err(syntax.clone()), "[1,2,3".parse::<Vector>());
+        assert_eq!(Err(syntax.clone()), "[1,,3]".parse::<Vector>());
+        assert_eq!(Err(syntax.clone()), "[1 2]".parse::<Vector>());
+        assert_eq!(Err(syntax), "[1,2] 3".parse::<Vector>());
+        assert_eq!(
+            Err(Error::TooFewDimensions {
+                type_name: "vector"
+            }),
+            "[]".parse::<Vector>()
+        );
+        assert_eq!(
+            Err(Error::NaN {
+                type_name: "vector"
+            }),
+            "[NaN]".parse::<Vector>()
+        );
+        assert_eq!(
+            Err(Error::Infinite {
+                type_name: "vector"
+            }),
+            "[-Infinity]".parse::<Vector>()
+        );
+        assert_eq!(
+            Err(Error::OutOfRange {
+                type_name: "vector",
+                value: "1e39".to_string()
+            }),
+            "[1e39]".parse::<Vector>()
+        );
+    }
+
+    #[test]
+    fn test_from_str_max_dimensions() {
+        let s = format!("[{}]", vec!["1"; 16000].join(","));
+        assert_eq!(16000, s.parse::<Vector>().unwrap().as_slice().len());
+
+        let s = format!("[{}]", vec!["1"; 16001].join(","));
+        assert_eq!(
+            Err(Error::TooManyDimensions {
+                type_name: "vector",
+                max: 16000
+            }),
+            s.parse::<Vector>()
+        );
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn test_serialize() {
@@ -104,4 +337,186 @@ mod tests {
         let vec: Vector = serde_json::from_str(json).unwrap();
         assert_eq!(vec, Vector::from(vec![1.0, 2.0, 3.0]));
     }
+
+    #[test]
+    fn test_distances() {
+        let a = Vector::from(vec![1.0, 1.0, 1.0]);
+        let b = Vector::from(vec![1.0, 1.0, 2.0]);
+        assert_eq!(1.0, a.l2_distance(&b).unwrap());
+        assert_eq!(4.0, a.inner_product(&b).unwrap());
+        assert_eq!(-4.0, a.max_inner_product(&b).unwrap());
+        assert!((a.cosine_distance(&b).unwrap() - 0.057190958417936644).abs() < 1e-9);
+        assert_eq!(1.0, a.l1_distance(&b).unwrap());
+    }
+
+    #[test]
+    fn test_cosine_distance_zero() {
+        let a = Vector::from(vec![1.0, 1.0]);
+        let b = Vector::from(vec![0.0, 0.0]);
+        assert!(a.cosine_distance(&b).unwrap().is_nan());
+        assert_eq!(0.0, a.cosine_distance(&a).unwrap());
+    }
+
+    #[test]
+    fn test_distance_different_dimensions() {
+        let a = Vector::from(vec![1.0, 2.0]);
+        let b = Vector::from(vec![1.0, 2.0, 3.0]);
+        let err = a.l2_distance(&b).unwrap_err();
+        assert_eq!(
+            Error::DifferentDimensions {
+                type_name: "vector",
+                left: 2,
+                right: 3
+            },
+            err
+        );
+        assert_eq!("different vector dimensions 2 and 3", err.to_string());
+    }
+
+    #[test]
+    fn test_ops() {
+        let a = Vector::from(vec![1.0, 2.0, 3.0]);
+        let b = Vector::from(vec![4.0, 5.0, 6.0]);
+        assert_eq!(Vector::from(vec![5.0, 7.0, 9.0]), (&a + &b).unwrap());
+        assert_eq!(Vector::from(vec![-3.0, -3.0, -3.0]), (&a - &b).unwrap());
+        assert_eq!(Vector::from(vec![4.0, 10.0, 18.0]), (&a * &b).unwrap());
+        assert_eq!(Vector::from(vec![2.0, 4.0, 6.0]), (&a * 2.0).unwrap());
+        assert_eq!(Vector::from(vec![5.0, 7.0, 9.0]), (a + b).unwrap());
+    }
+
+    #[test]
+    fn test_assign_ops() {
+        let mut a = Vector::from(vec![1.0, 2.0, 3.0]);
+        a += Vector::from(vec![1.0, 1.0, 1.0]);
+        a -= &Vector::from(vec![0.5, 0.5, 0.5]);
+        a *= Vector::from(vec![2.0, 2.0, 2.0]);
+        a *= 0.5;
+        assert_eq!(Vector::from(vec![1.5, 2.5, 3.5]), a);
+    }
+
+    #[test]
+    #[should_panic(expected = "different vector dimensions 2 and 3")]
+    fn test_assign_ops_different_dimensions() {
+        let mut a = Vector::from(vec![1.0, 2.0]);
+        a += Vector::from(vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_ops_errors() {
+        let a = Vector::from(vec![1.0, 2.0]);
+        let b = Vector::from(vec![1.0, 2.0, 3.0]);
+        assert_eq!(
+            Err(Error::DifferentDimensions {
+                type_name: "vector",
+                left: 2,
+                right: 3
+            }),
+            &a + &b
+        );
+
+        let a = Vector::from(vec![f32::MAX]);
+        assert_eq!(Err(Error::Overflow), &a + &a);
+        assert_eq!(Err(Error::Overflow), &a * 2.0);
+
+        let a = Vector::from(vec![1e-30]);
+        assert_eq!(Err(Error::Underflow), &a * &a);
+        assert_eq!(Vector::from(vec![0.0]), (&a * 0.0).unwrap());
+    }
+
+    #[test]
+    fn test_concat() {
+        let a = Vector::from(vec![1.0, 2.0]);
+        let b = Vector::from(vec![3.0]);
+        assert_eq!(Vector::from(vec![1.0, 2.0, 3.0]), a.concat(&b).unwrap());
+
+        let a = Vector::from(vec![1.0; 16000]);
+        assert_eq!(
+            Err(Error::TooManyDimensions {
+                type_name: "vector",
+                max: 16000
+            }),
+            a.concat(&b)
+        );
+    }
+
+    #[test]
+    fn test_dimensions() {
+        let vec = Vector::from(vec![1.0, 2.0, 3.0]);
+        assert_eq!(3, vec.dimensions());
+    }
+
+    #[test]
+    fn test_norm() {
+        let vec = Vector::from(vec![3.0, 4.0]);
+        assert_eq!(5.0, vec.norm());
+        assert_eq!(0.0, Vector::from(vec![0.0, 0.0]).norm());
+    }
+
+    #[test]
+    fn test_l2_normalize() {
+        let vec = Vector::from(vec![3.0, 4.0]);
+        assert_eq!(Vector::from(vec![0.6, 0.8]), vec.l2_normalize());
+
+        let vec = Vector::from(vec![0.0, 0.0]);
+        assert_eq!(vec, vec.l2_normalize());
+    }
+
+    #[test]
+    fn test_subvector() {
+        let vec = Vector::from(vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(vec![2.0, 3.0, 4.0], vec.subvector(2, 3).unwrap().to_vec());
+        assert_eq!(vec![1.0], vec.subvector(0, 2).unwrap().to_vec());
+        assert_eq!(vec![4.0, 5.0], vec.subvector(4, 10).unwrap().to_vec());
+        assert_eq!(vec.to_vec(), vec.subvector(-5, 20).unwrap().to_vec());
+        assert_eq!(vec![5.0], vec.subvector(5, i32::MAX).unwrap().to_vec());
+
+        let error = Err(Error::TooFewDimensions {
+            type_name: "vector",
+        });
+        assert_eq!(error, vec.subvector(6, 1));
+        assert_eq!(error, vec.subvector(1, 0));
+        assert_eq!(error, vec.subvector(-1, 2));
+        assert_eq!(error, vec.subvector(i32::MIN, i32::MAX));
+    }
+
+    #[test]
+    fn test_binary_quantize() {
+        let vec = Vector::from(vec![1.0, -1.0, 0.0, -0.0, 0.5, 2.0, -3.0, 1e-30, 4.0]);
+        let bit = vec.binary_quantize();
+        assert_eq!(9, bit.len());
+        assert_eq!(&[0b10001101, 0b10000000], bit.as_bytes());
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(
+            Vector::from(vec![1.0, 2.0, 3.0]),
+            Vector::try_new(vec![1.0, 2.0, 3.0]).unwrap()
+        );
+        assert_eq!(
+            Err(Error::TooFewDimensions {
+                type_name: "vector"
+            }),
+            Vector::try_new(vec![])
+        );
+        assert_eq!(
+            Err(Error::TooManyDimensions {
+                type_name: "vector",
+                max: 16000
+            }),
+            Vector::try_new(vec![1.0; 16001])
+        );
+        assert_eq!(
+            Err(Error::NaN {
+                type_name: "vector"
+            }),
+            Vector::try_new(vec![1.0, f32::NAN])
+        );
+        assert_eq!(
+            Err(Error::Infinite {
+                type_name: "vector"
+            }),
+            Vector::try_new(vec![f32::INFINITY])
+        );
+    }
 }
@@ -0,0 +1,217 @@
+#[cfg(feature = "diesel")]
+use crate::diesel_ext::vector::VectorType;
+
+#[cfg(feature = "diesel")]
+use diesel::expression::AsExpression;
+
+use crate::Vector;
+
+#[cfg(any(feature = "postgres", feature = "sqlx", feature = "diesel"))]
+use crate::Error;
+
+/// A borrowed vector.
+///
+/// Borrows either a slice of values or the binary representation of a row, which is decoded lazily.
+/// With the `ndarray` feature, it can also borrow a (possibly strided) `ArrayView1`.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "diesel", derive(AsExpression))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = VectorType))]
+pub struct VectorRef<'a>(Repr<'a>);
+
+#[derive(Clone, Copy, Debug)]
+enum Repr<'a> {
+    Slice(&'a [f32]),
+    // big-endian values, only decoded by integrations
+    #[cfg_attr(
+        not(any(feature = "postgres", feature = "sqlx", feature = "diesel")),
+        allow(dead_code)
+    )]
+    Bytes(&'a [u8]),
+    #[cfg(feature = "ndarray")]
+    View(ndarray::ArrayView1<'a, f32>),
+}
+
+impl<'a> From<&'a [f32]> for VectorRef<'a> {
+    fn from(v: &'a [f32]) -> Self {
+        VectorRef(Repr::Slice(v))
+    }
+}
+
+impl<'a> From<&'a Vector> for VectorRef<'a> {
+    fn from(v: &'a Vector) -> Self {
+        VectorRef(Repr::Slice(v.as_slice()))
+    }
+}
+
+impl<'a> VectorRef<'a> {
+    /// Returns the number of dimensions.
+    pub fn len(&self) -> usize {
+        match self.0 {
+            Repr::Slice(v) => v.len(),
+            Repr::Bytes(v) => v.len() / 4,
+            #[cfg(feature = "ndarray")]
+            Repr::View(v) => v.len(),
+        }
+    }
+
+    /// Returns whether the vector is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the values.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = f32> + 'a {
+        match self.0 {
+            Repr::Slice(v) => Iter::Slice(v.iter()),
+            Repr::Bytes(v) => Iter::Bytes(v.chunks_exact(4)),
+            #[cfg(feature = "ndarray")]
+            Repr::View(v) => Iter::View(v.into_iter()),
+        }
+    }
+
+    /// Copies the values into a slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slice has a different length.
+    pub fn copy_to_slice(&self, dst: &mut [f32]) {
+        assert_eq!(
+            self.len(),
+            dst.len(),
+            "destination and source slices have different lengths"
+        );
+        match self.0 {
+            Repr::Slice(v) => dst.copy_from_slice(v),
+            _ => {
+                for (d, v) in dst.iter_mut().zip(self.iter()) {
+                    *d = v;
+                }
+            }
+        }
+    }
+
+    /// Returns an owned vector.
+    pub fn to_vector(&self) -> Vector {
+        Vector::from(self.iter().collect::<Vec<f32>>())
+    }
+
+    #[cfg(feature = "ndarray")]
+    pub(crate) fn from_view(v: ndarray::ArrayView1<'a, f32>) -> VectorRef<'a> {
+        VectorRef(Repr::View(v))
+    }
+
+    #[cfg(any(feature = "postgres", feature = "sqlx", feature = "diesel"))]
+    pub(crate) fn from_sql(buf: &'a [u8]) -> Result<VectorRef<'a>, Error> {
+        if buf.len() < 4 {
+            return Err(Error::Truncated {
+                type_name: "vector",
+                expected: 4,
+                actual: buf.len(),
+            });
+        }
+
+        let dim = usize::from(u16::from_be_bytes([buf[0], buf[1]]));
+        let unused = u16::from_be_bytes([buf[2], buf[3]]);
+        if unused != 0 {
+            return Err(Error::NonZeroUnused {
+                type_name: "vector",
+                value: unused.into(),
+            });
+        }
+
+        let expected = 4 + 4 * dim;
+        if buf.len() != expected {
+            return Err(Error::InvalidLength {
+                type_name: "vector",
+                expected,
+                actual: buf.len(),
+            });
+        }
+
+        Ok(VectorRef(Repr::Bytes(&buf[4..])))
+    }
+}
+
+impl PartialEq for VectorRef<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+enum Iter<'a> {
+    Slice(std::slice::Iter<'a, f32>),
+    Bytes(std::slice::ChunksExact<'a, u8>),
+    #[cfg(feature = "ndarray")]
+    View(ndarray::iter::Iter<'a, f32, ndarray::Ix1>),
+}
+
+impl Iterator for Iter<'_> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        match self {
+            Iter::Slice(it) => it.next().copied(),
+            Iter::Bytes(it) => it
+                .next()
+                .map(|v| f32::from_be_bytes([v[0], v[1], v[2], v[3]])),
+            #[cfg(feature = "ndarray")]
+            Iter::View(it) => it.next().copied(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Iter::Slice(it) => it.size_hint(),
+            Iter::Bytes(it) => it.size_hint(),
+            #[cfg(feature = "ndarray")]
+            Iter::View(it) => it.size_hint(),
+        }
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Vector, VectorRef};
+
+    #[test]
+    fn test_from_slice() {
+        let data = [1.0, 2.0, 3.0];
+        let vec = VectorRef::from(&data[..]);
+        assert_eq!(3, vec.len());
+        assert_eq!(vec![1.0, 2.0, 3.0], vec.iter().collect::<Vec<f32>>());
+        assert_eq!(Vector::from(vec![1.0, 2.0, 3.0]), vec.to_vector());
+    }
+
+    #[test]
+    fn test_copy_to_slice() {
+        let vec = Vector::from(vec![1.0, 2.0, 3.0]);
+        let mut dst = [0.0; 3];
+        VectorRef::from(&vec).copy_to_slice(&mut dst);
+        assert_eq!([1.0, 2.0, 3.0], dst);
+    }
+
+    #[test]
+    #[should_panic(expected = "destination and source slices have different lengths")]
+    fn test_copy_to_slice_different_lengths() {
+        let vec = Vector::from(vec![1.0, 2.0, 3.0]);
+        VectorRef::from(&vec).copy_to_slice(&mut [0.0; 2]);
+    }
+
+    #[cfg(any(feature = "postgres", feature = "sqlx", feature = "diesel"))]
+    #[test]
+    fn test_from_sql() {
+        let buf = [0, 2, 0, 0, 63, 128, 0, 0, 64, 0, 0, 0];
+        let vec = VectorRef::from_sql(&buf).unwrap();
+        assert_eq!(2, vec.len());
+        assert_eq!(vec![1.0, 2.0], vec.iter().collect::<Vec<f32>>());
+
+        let mut dst = [0.0; 2];
+        vec.copy_to_slice(&mut dst);
+        assert_eq!([1.0, 2.0], dst);
+
+        let data = [1.0, 2.0];
+        assert_eq!(VectorRef::from(&data[..]), vec);
+    }
+}